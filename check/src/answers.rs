@@ -0,0 +1,75 @@
+use std::{io::ErrorKind, path::Path};
+
+// Known-good answers for a day, one `partN: answer` line per part. Blank lines
+// and `#` comments are ignored.
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+impl Answers {
+    pub fn parse(contents: &str) -> common::Result<Self> {
+        let mut answers = Answers::default();
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| format!("line {}: expected `partN: answer`", number + 1))?;
+            let value = Some(value.trim().to_string());
+            match key.trim() {
+                "part1" => answers.part1 = value,
+                "part2" => answers.part2 = value,
+                other => return Err(format!("line {}: unknown key `{}`", number + 1, other).into()),
+            }
+        }
+        Ok(answers)
+    }
+
+    // a missing file just means nothing has been recorded yet
+    pub fn load(path: &Path) -> common::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => {
+                Self::parse(&contents).map_err(|e| format!("{}: {}", path.display(), e).into())
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(format!("failed to read {}: {}", path.display(), e).into()),
+        }
+    }
+
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_answers() {
+        let answers = Answers::parse("# day 21\npart1: 2098\n\npart2: a,b,c\n").unwrap();
+        assert_eq!(Some("2098"), answers.expected(1));
+        assert_eq!(Some("a,b,c"), answers.expected(2));
+    }
+
+    #[test]
+    fn parse_partial_answers() {
+        let answers = Answers::parse("part1: 42").unwrap();
+        assert_eq!(Some("42"), answers.expected(1));
+        assert_eq!(None, answers.expected(2));
+    }
+
+    #[test]
+    fn parse_rejects_unknown_key() {
+        assert!(Answers::parse("part3: 1").is_err());
+        assert!(Answers::parse("part1 1").is_err());
+    }
+}
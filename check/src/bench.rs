@@ -0,0 +1,182 @@
+use std::time::{Duration, Instant};
+
+use common::{params::Settings, Solution};
+use serde::{Deserialize, Serialize};
+
+// changes smaller than this are timer noise, whatever the relative difference
+const NOISE_FLOOR_MS: f64 = 1.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PhaseTiming {
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub mean_ms: f64,
+}
+
+impl PhaseTiming {
+    fn from_samples(samples: &mut [Duration]) -> Self {
+        samples.sort();
+        let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+        let total: Duration = samples.iter().sum();
+        PhaseTiming {
+            min_ms: ms(samples[0]),
+            median_ms: ms(samples[samples.len() / 2]),
+            mean_ms: ms(total) / samples.len() as f64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DayTiming {
+    pub day: u8,
+    pub parse: PhaseTiming,
+    pub part1: PhaseTiming,
+    pub part2: PhaseTiming,
+}
+
+impl DayTiming {
+    pub fn phases(&self) -> [(&'static str, PhaseTiming); 3] {
+        [
+            ("parse", self.parse),
+            ("part1", self.part1),
+            ("part2", self.part2),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    pub iterations: usize,
+    pub days: Vec<DayTiming>,
+}
+
+// time each phase separately; each iteration parses afresh so that the parts
+// never see a warmed-up model
+pub fn time_solution<S: Solution>(
+    number: u8,
+    input: &str,
+    iterations: usize,
+    settings: &Settings,
+) -> common::Result<DayTiming> {
+    let params = settings.load(number)?;
+    let mut parse = Vec::with_capacity(iterations);
+    let mut part1 = Vec::with_capacity(iterations);
+    let mut part2 = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let parsed = S::parse(input)?;
+        parse.push(start.elapsed());
+
+        let start = Instant::now();
+        S::part1_with(&parsed, &params)?;
+        part1.push(start.elapsed());
+
+        let start = Instant::now();
+        S::part2_with(&parsed, &params)?;
+        part2.push(start.elapsed());
+    }
+    Ok(DayTiming {
+        day: number,
+        parse: PhaseTiming::from_samples(&mut parse),
+        part1: PhaseTiming::from_samples(&mut part1),
+        part2: PhaseTiming::from_samples(&mut part2),
+    })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Regression {
+    pub day: u8,
+    pub phase: &'static str,
+    pub baseline_ms: f64,
+    pub current_ms: f64,
+}
+
+// compare medians; a phase regresses when it is slower than the baseline by
+// more than the tolerance (a fraction, e.g. 0.2 for 20%)
+pub fn regressions(baseline: &Report, current: &Report, tolerance: f64) -> Vec<Regression> {
+    let mut found = Vec::new();
+    for day in current.days.iter() {
+        let base = match baseline.days.iter().find(|b| b.day == day.day) {
+            Some(b) => b,
+            None => continue,
+        };
+        for ((phase, now), (_, then)) in day.phases().iter().zip(base.phases().iter()) {
+            let limit = then.median_ms * (1.0 + tolerance);
+            if now.median_ms > limit && now.median_ms - then.median_ms > NOISE_FLOOR_MS {
+                found.push(Regression {
+                    day: day.day,
+                    phase,
+                    baseline_ms: then.median_ms,
+                    current_ms: now.median_ms,
+                });
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(median_ms: f64) -> PhaseTiming {
+        PhaseTiming {
+            min_ms: median_ms,
+            median_ms,
+            mean_ms: median_ms,
+        }
+    }
+
+    fn report(day: u8, part2_ms: f64) -> Report {
+        Report {
+            iterations: 1,
+            days: vec![DayTiming {
+                day,
+                parse: timing(1.0),
+                part1: timing(10.0),
+                part2: timing(part2_ms),
+            }],
+        }
+    }
+
+    #[test]
+    fn phase_timing_statistics() {
+        let mut samples = [3, 1, 2].map(Duration::from_millis);
+        let t = PhaseTiming::from_samples(&mut samples);
+        assert_eq!(timing(2.0).median_ms, t.median_ms);
+        assert_eq!(1.0, t.min_ms);
+        assert_eq!(2.0, t.mean_ms);
+    }
+
+    #[test]
+    fn slower_phase_is_a_regression() {
+        let found = regressions(&report(15, 100.0), &report(15, 150.0), 0.2);
+        assert_eq!(
+            vec![Regression {
+                day: 15,
+                phase: "part2",
+                baseline_ms: 100.0,
+                current_ms: 150.0
+            }],
+            found
+        );
+    }
+
+    #[test]
+    fn within_tolerance_or_noise_is_not_a_regression() {
+        assert!(regressions(&report(15, 100.0), &report(15, 110.0), 0.2).is_empty());
+        assert!(regressions(&report(15, 0.1), &report(15, 0.5), 0.2).is_empty());
+    }
+
+    #[test]
+    fn days_missing_from_baseline_are_skipped() {
+        assert!(regressions(&report(15, 100.0), &report(23, 500.0), 0.2).is_empty());
+    }
+
+    #[test]
+    fn report_round_trips_through_json() {
+        let original = report(23, 123.5);
+        let json = serde_json::to_string(&original).unwrap();
+        assert_eq!(original, serde_json::from_str(&json).unwrap());
+    }
+}
@@ -0,0 +1,312 @@
+use std::{
+    any::TypeId,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use common::{input::Source, params::Settings, Answer, Metadata, Solution};
+use generate::{Generate, Generated, Validity};
+use render::{Animate, Frame};
+use viewer::{Simulation, Watch};
+
+use crate::bench::{self, DayTiming};
+
+pub struct Day {
+    pub number: u8,
+    solve: Solve,
+    solve_model: Solve,
+    model: fn(&str) -> common::Result<String>,
+    params: fn(&Settings, u8) -> common::Result<String>,
+    time: fn(u8, &str, usize, &Settings) -> common::Result<DayTiming>,
+    animate: Option<Frames>,
+    watch: Option<Simulate>,
+    generate: Option<Generator>,
+}
+
+// parses the input (or its JSON model) and answers the given parts, with the
+// day's parameters
+type Solve = fn(&str, &[u8], &Settings, u8) -> common::Result<Vec<PartResult>>;
+
+// parses the input and draws the frames of a part, with the day's parameters
+type Frames = fn(&str, u8, &Settings, u8) -> common::Result<Vec<Frame>>;
+
+// parses the input and sets up the simulation of a part, with the day's
+// parameters
+type Simulate = fn(&str, u8, &Settings, u8) -> common::Result<Box<dyn Simulation>>;
+
+// makes up an input from a seed, at a size (or the typical size if none)
+type Generator = fn(u64, Option<usize>, Validity) -> common::Result<Generated>;
+
+impl Day {
+    // parse the input once, then answer each of the requested parts
+    pub fn run(
+        &self,
+        source: &Source,
+        parts: &[u8],
+        settings: &Settings,
+    ) -> common::Result<Vec<PartResult>> {
+        let input = source.read()?;
+        (self.solve)(&input, parts, settings, self.number).map_err(|e| source.locate(e))
+    }
+
+    /// Answers from a parsed input saved as JSON, rather than the text.
+    pub fn run_model(
+        &self,
+        source: &Source,
+        parts: &[u8],
+        settings: &Settings,
+    ) -> common::Result<Vec<PartResult>> {
+        let json = source.read()?;
+        (self.solve_model)(&json, parts, settings, self.number).map_err(|e| source.locate(e))
+    }
+
+    /// The parsed input, as JSON.
+    pub fn model(&self, source: &Source) -> common::Result<String> {
+        let input = source.read()?;
+        (self.model)(&input).map_err(|e| source.locate(e))
+    }
+
+    /// The parameters the day would run with, as TOML.
+    pub fn params(&self, settings: &Settings) -> common::Result<String> {
+        (self.params)(settings, self.number)
+    }
+
+    pub fn time(
+        &self,
+        source: &Source,
+        iterations: usize,
+        settings: &Settings,
+    ) -> common::Result<DayTiming> {
+        let input = source.read()?;
+        (self.time)(self.number, &input, iterations, settings).map_err(|e| source.locate(e))
+    }
+
+    /// The frames of a part, for the days that can be animated.
+    pub fn frames(
+        &self,
+        source: &Source,
+        part: u8,
+        settings: &Settings,
+    ) -> common::Result<Vec<Frame>> {
+        let animate = self
+            .animate
+            .ok_or_else(|| format!("day {} has no animation", self.number))?;
+        let input = source.read()?;
+        animate(&input, part, settings, self.number).map_err(|e| source.locate(e))
+    }
+
+    /// The simulation of a part, for the days that can be watched.
+    pub fn simulation(
+        &self,
+        source: &Source,
+        part: u8,
+        settings: &Settings,
+    ) -> common::Result<Box<dyn Simulation>> {
+        let watch = self
+            .watch
+            .ok_or_else(|| format!("day {} has nothing to watch", self.number))?;
+        let input = source.read()?;
+        watch(&input, part, settings, self.number).map_err(|e| source.locate(e))
+    }
+
+    /// A random input, for the days that can make them up.
+    pub fn generate(
+        &self,
+        seed: u64,
+        size: Option<usize>,
+        validity: Validity,
+    ) -> common::Result<Generated> {
+        let generate = self
+            .generate
+            .ok_or_else(|| format!("day {} has no input generator", self.number))?;
+        generate(seed, size, validity)
+    }
+
+    // recorded answers sit next to the day's input, so an alternative input
+    // directory carries its own answers
+    pub fn answers_file(&self) -> PathBuf {
+        common::input::day_dir(self.number).join("answers.txt")
+    }
+}
+
+// a part's answer, as text, along with how long the part took (excluding
+// parsing) and any metadata the solver attached
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: String,
+    pub elapsed: Duration,
+    pub metadata: Metadata,
+}
+
+impl PartResult {
+    fn time<A: Answer>(
+        part: u8,
+        solve: impl FnOnce() -> common::Result<A>,
+    ) -> common::Result<Self> {
+        let start = Instant::now();
+        let answer = solve()?;
+        Ok(PartResult {
+            part,
+            elapsed: start.elapsed(),
+            answer: answer.to_string(),
+            metadata: answer.metadata(),
+        })
+    }
+}
+
+fn solve<S: Solution>(
+    input: &str,
+    parts: &[u8],
+    settings: &Settings,
+    number: u8,
+) -> common::Result<Vec<PartResult>> {
+    let params = settings.load(number)?;
+    answer::<S>(&S::parse(input)?, &params, parts)
+}
+
+fn solve_model<S: Solution>(
+    json: &str,
+    parts: &[u8],
+    settings: &Settings,
+    number: u8,
+) -> common::Result<Vec<PartResult>> {
+    let params = settings.load(number)?;
+    let parsed: S::Input =
+        serde_json::from_str(json).map_err(|e| format!("not a day's parsed input: {}", e))?;
+    S::validate(&parsed).map_err(|e| format!("not a valid parsed input: {}", e))?;
+    answer::<S>(&parsed, &params, parts)
+}
+
+fn model<S: Solution>(input: &str) -> common::Result<String> {
+    Ok(serde_json::to_string_pretty(&S::parse(input)?)?)
+}
+
+fn params<S: Solution>(settings: &Settings, number: u8) -> common::Result<String> {
+    if TypeId::of::<S::Params>() == TypeId::of::<()>() {
+        return Err(format!("day {} has no parameters", number).into());
+    }
+    let params: S::Params = settings.load(number)?;
+    Ok(toml::to_string(&params)?)
+}
+
+fn answer<S: Solution>(
+    parsed: &S::Input,
+    params: &S::Params,
+    parts: &[u8],
+) -> common::Result<Vec<PartResult>> {
+    parts
+        .iter()
+        .map(|&part| match part {
+            1 => PartResult::time(part, || S::part1_with(parsed, params)),
+            2 => PartResult::time(part, || S::part2_with(parsed, params)),
+            _ => Err(format!("there is no part {}", part).into()),
+        })
+        .collect()
+}
+
+fn frames<S: Animate>(
+    input: &str,
+    part: u8,
+    settings: &Settings,
+    number: u8,
+) -> common::Result<Vec<Frame>> {
+    let params = settings.load(number)?;
+    S::frames(&S::parse(input)?, &params, part)
+}
+
+fn simulation<S: Watch>(
+    input: &str,
+    part: u8,
+    settings: &Settings,
+    number: u8,
+) -> common::Result<Box<dyn Simulation>> {
+    let params = settings.load(number)?;
+    S::simulation(&S::parse(input)?, &params, part)
+}
+
+fn generated<S: Generate>(
+    seed: u64,
+    size: Option<usize>,
+    validity: Validity,
+) -> common::Result<Generated> {
+    generate::generate::<S>(seed, size.unwrap_or(S::DEFAULT_SIZE), validity)
+}
+
+macro_rules! day {
+    ($number:expr, $solution:ty) => {
+        Day {
+            number: $number,
+            solve: solve::<$solution>,
+            solve_model: solve_model::<$solution>,
+            model: model::<$solution>,
+            params: params::<$solution>,
+            time: bench::time_solution::<$solution>,
+            animate: None,
+            watch: None,
+            generate: None,
+        }
+    };
+    ($number:expr, $solution:ty, animated) => {
+        Day {
+            animate: Some(frames::<$solution>),
+            ..day!($number, $solution)
+        }
+    };
+    ($number:expr, $solution:ty, watched) => {
+        Day {
+            watch: Some(simulation::<$solution>),
+            ..day!($number, $solution)
+        }
+    };
+    ($number:expr, $solution:ty, animated, watched) => {
+        Day {
+            watch: Some(simulation::<$solution>),
+            ..day!($number, $solution, animated)
+        }
+    };
+    ($number:expr, $solution:ty, generated) => {
+        Day {
+            generate: Some(generated::<$solution>),
+            ..day!($number, $solution)
+        }
+    };
+    ($number:expr, $solution:ty, animated, generated) => {
+        Day {
+            generate: Some(generated::<$solution>),
+            ..day!($number, $solution, animated)
+        }
+    };
+}
+
+pub static DAYS: [Day; 25] = [
+    day!(1, day01::Day01),
+    day!(2, day02::Day02),
+    day!(3, day03::Day03),
+    day!(4, day04::Day04, generated),
+    day!(5, day05::Day05),
+    day!(6, day06::Day06),
+    day!(7, day07::Day07, generated),
+    day!(8, day08::Day08, generated),
+    day!(9, day09::Day09),
+    day!(10, day10::Day10),
+    day!(11, day11::Day11, animated, watched),
+    day!(12, day12::Day12),
+    day!(13, day13::Day13),
+    day!(14, day14::Day14),
+    day!(15, day15::Day15),
+    day!(16, day16::Day16, generated),
+    day!(17, day17::Day17, animated, watched),
+    day!(18, day18::Day18),
+    day!(19, day19::Day19),
+    day!(20, day20::Day20, generated),
+    day!(21, day21::Day21),
+    day!(22, day22::Day22, watched),
+    day!(23, day23::Day23, watched),
+    day!(24, day24::Day24, animated, generated),
+    day!(25, day25::Day25),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
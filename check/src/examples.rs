@@ -0,0 +1,178 @@
+// Every `example*.txt` shipped in a day's directory is run through that day's
+// solver and checked against its `.answers` sidecar, which records the expected
+// answer for each part the example applies to. A sidecar with no parts (just a
+// comment saying why) marks an example that can't be solved on its own. An
+// example whose puzzle constants differ from the real input's has them in a
+// `.toml` sidecar too.
+
+use std::path::{Path, PathBuf};
+
+use common::{input::Source, params::Settings};
+
+use crate::{answers::Answers, days};
+
+// the examples shipped in this repository, whatever AOC_INPUT_DIR says
+fn day_dir(number: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("day{:02}", number))
+}
+
+fn examples(dir: &Path) -> Vec<PathBuf> {
+    let mut found: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| {
+                    let name = p.file_name().and_then(|n| n.to_str()).unwrap_or("");
+                    name.starts_with("example") && name.ends_with(".txt")
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    found.sort();
+    found
+}
+
+// how an example is fed to its day
+#[derive(Clone, Copy)]
+enum Via {
+    Text,
+    // parsed, saved as JSON and answered from that
+    Model,
+    // rewritten as if saved on Windows by a careless editor
+    Messy,
+}
+
+// a scratch copy of something derived from `example`, removed when dropped
+struct Scratch(PathBuf);
+
+impl Scratch {
+    fn write(day: &days::Day, example: &Path, ext: &str, contents: &str) -> common::Result<Self> {
+        let stem = example
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("example");
+        let path = std::env::temp_dir().join(format!(
+            "check-{}-day{:02}-{}.{}",
+            std::process::id(),
+            day.number,
+            stem,
+            ext
+        ));
+        std::fs::write(&path, contents)?;
+        Ok(Scratch(path))
+    }
+
+    fn source(&self) -> Source {
+        Source::File(self.0.clone())
+    }
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+// CRLF line endings, stray spaces at the end of every line (blank ones too)
+// and a few blank lines at the end
+fn messy(text: &str) -> String {
+    let lines: Vec<String> = text.lines().map(|l| format!("{} \t ", l)).collect();
+    lines.join("\r\n") + "\r\n\r\n  \r\n\r\n"
+}
+
+// returns the number of parts checked
+fn check_example(day: &days::Day, example: &Path, via: Via) -> common::Result<usize> {
+    let sidecar = example.with_extension("answers");
+    let expected = std::fs::read_to_string(&sidecar)
+        .map_err(|e| format!("no sidecar {}: {}", sidecar.display(), e))?;
+    let expected = Answers::parse(&expected)?;
+
+    let parts: Vec<u8> = [1, 2]
+        .iter()
+        .copied()
+        .filter(|&p| expected.expected(p).is_some())
+        .collect();
+    if parts.is_empty() {
+        return Ok(0);
+    }
+
+    let params = example.with_extension("toml");
+    let settings = Settings {
+        file: Some(params).filter(|p| p.exists()),
+        set: Vec::new(),
+    };
+
+    let source = Source::File(example.to_path_buf());
+    let actual = match via {
+        Via::Text => day.run(&source, &parts, &settings)?,
+        Via::Model => {
+            let saved = Scratch::write(day, example, "json", &day.model(&source)?)?;
+            day.run_model(&saved.source(), &parts, &settings)?
+        }
+        Via::Messy => {
+            let saved = Scratch::write(day, example, "txt", &messy(&source.read()?))?;
+            day.run(&saved.source(), &parts, &settings)?
+        }
+    };
+    for (&part, actual) in parts.iter().zip(actual.iter()) {
+        let expected = expected.expected(part).unwrap_or_default();
+        if expected != actual.answer {
+            return Err(format!(
+                "part {}: expected {}, got {}",
+                part, expected, actual.answer
+            )
+            .into());
+        }
+    }
+    Ok(parts.len())
+}
+
+fn check_all(via: Via) {
+    let mut failures = Vec::new();
+    let mut checked = 0;
+    for day in days::DAYS.iter() {
+        for example in examples(&day_dir(day.number)) {
+            match check_example(day, &example, via) {
+                Ok(parts) => checked += parts,
+                Err(e) => failures.push(format!("{}: {}", example.display(), e)),
+            }
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    assert!(checked > 0, "no examples found");
+}
+
+#[test]
+fn examples_match_expected() {
+    check_all(Via::Text);
+}
+
+#[test]
+fn examples_survive_a_json_round_trip() {
+    check_all(Via::Model);
+}
+
+#[test]
+fn examples_ignore_line_endings_and_trailing_whitespace() {
+    check_all(Via::Messy);
+}
+
+#[test]
+fn models_the_parser_would_reject_are_refused() {
+    let bad = [
+        (10, r#"{"adapters":[],"final_joltage":3}"#),
+        (23, "[1,1,7]"),
+        (23, "[1,2,3]"),
+    ];
+    for &(number, json) in bad.iter() {
+        let day = days::DAYS.iter().find(|d| d.number == number).unwrap();
+        let saved = Scratch::write(day, Path::new("invalid"), "json", json).unwrap();
+        let settings = Settings::default();
+        match day.run_model(&saved.source(), &[1, 2], &settings) {
+            Ok(_) => panic!("day {} accepted {}", number, json),
+            Err(e) => assert!(e.to_string().contains("not a valid parsed input"), "{}", e),
+        }
+    }
+}
@@ -1,3 +1,583 @@
-fn main() {
-    println!("Hello, world!");
+use std::{path::PathBuf, time::Duration};
+
+use clap::{Args, Parser, Subcommand};
+use common::{
+    input::Source,
+    logging::{self, Verbosity},
+    params::Settings,
+};
+use generate::Validity;
+
+mod answers;
+mod bench;
+mod days;
+#[cfg(test)]
+mod examples;
+mod output;
+mod parallel;
+
+use answers::Answers;
+use bench::Report;
+use output::Format;
+
+/// Runs the Advent of Code 2020 solvers from one place
+#[derive(Parser)]
+struct Cli {
+    /// Diagnostic output on stderr: quiet, info, debug or trace; defaults to
+    /// `AOC_LOG`, or quiet if that is unset
+    #[arg(long, global = true)]
+    log: Option<Verbosity>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+// puzzle constants to change, for the days that have any
+#[derive(Args)]
+struct ParamArgs {
+    /// TOML file of parameters; defaults to the day's `params.toml` next to
+    /// its input, if there is one
+    #[arg(long)]
+    params: Option<PathBuf>,
+    /// Set a parameter, over the file's value; may be repeated
+    #[arg(long = "set", value_name = "NAME=VALUE")]
+    set: Vec<String>,
+}
+
+impl From<ParamArgs> for Settings {
+    fn from(args: ParamArgs) -> Self {
+        Settings {
+            file: args.params,
+            set: args.set,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a day's solver and print its answers
+    Run {
+        /// Day to run
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Part to run; runs both parts if omitted
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Puzzle input, or `-` for stdin; defaults to the day's `input.txt`
+        /// under `AOC_INPUT_DIR`, or in this repository if that is unset
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Parsed input saved by `parse`, or `-` for stdin, to answer from
+        /// instead of the puzzle input
+        #[arg(long, conflicts_with = "input")]
+        model: Option<PathBuf>,
+        /// Output format; json and ndjson include timings and any answer metadata
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        #[command(flatten)]
+        params: ParamArgs,
+    },
+    /// Print the parameters a day would run with, as TOML
+    Params {
+        /// Day whose parameters to print
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[command(flatten)]
+        params: ParamArgs,
+    },
+    /// Parse a day's input and print the parsed model as JSON
+    Parse {
+        /// Day to parse
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Puzzle input, or `-` for stdin; defaults as for `run`
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// File to write the JSON to, instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Export the frames of a simulation (days 11, 17 and 24) as images
+    Animate {
+        /// Day to animate
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Part to animate
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Puzzle input, or `-` for stdin; defaults as for `run`
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Image format: png or svg for a directory of numbered frames, or
+        /// gif for one animated file
+        #[arg(long, default_value = "gif")]
+        format: render::Format,
+        /// GIF file, or directory for the frames
+        #[arg(long)]
+        output: PathBuf,
+        /// Size of a cell, in pixels
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..))]
+        scale: u32,
+        /// Time each frame of a GIF is shown, in milliseconds, rounded up to
+        /// hundredths of a second
+        #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u16).range(1..))]
+        delay: u16,
+        #[command(flatten)]
+        params: ParamArgs,
+    },
+    /// Watch a simulation (days 11, 17, 22 and 23) play out in the terminal
+    Watch {
+        /// Day to watch
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Part to watch
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Puzzle input, or `-` for stdin; defaults as for `run`
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Time between steps while playing, in milliseconds
+        #[arg(long, default_value_t = 200)]
+        delay: u64,
+        #[command(flatten)]
+        params: ParamArgs,
+    },
+    /// Make up a random input (days 4, 7, 8, 16, 20 and 24), the same for the
+    /// same seed and size
+    Generate {
+        /// Day to make an input for
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Seed for the random numbers
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Number of passports, rules, instructions and so on; defaults to
+        /// about the size of a real input
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        size: Option<u64>,
+        /// Spoil one line, so that the input fails to parse
+        #[arg(long)]
+        invalid: bool,
+        /// Directory to write `dayNN/input.txt` and the known answers to, for
+        /// use as `AOC_INPUT_DIR`; prints the input if omitted
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Run every day in parallel and summarise the answers, timings and errors
+    All {
+        /// Number of worker threads; defaults to the available parallelism
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        threads: Option<u64>,
+    },
+    /// Run solvers against their inputs and compare with the recorded answers
+    Verify {
+        /// Day to verify; verifies every day if omitted
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+    },
+    /// Time parsing and each part separately, and compare with a baseline
+    Bench {
+        /// Day to benchmark; benchmarks every day if omitted
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        /// Number of timed runs of each phase
+        #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u64).range(1..))]
+        iterations: u64,
+        /// Write the results as JSON to this file
+        #[arg(long)]
+        output: Option<PathBuf>,
+        /// Earlier results to compare against; slower phases are reported as regressions
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+        /// Fraction by which a phase may be slower than the baseline
+        #[arg(long, default_value_t = 0.2)]
+        tolerance: f64,
+    },
+}
+
+fn run(
+    day: u8,
+    part: Option<u8>,
+    input: Option<PathBuf>,
+    model: Option<PathBuf>,
+    format: Format,
+    settings: Settings,
+) -> common::Result<()> {
+    let day = days::find(day).ok_or_else(|| format!("unknown day {}", day))?;
+
+    let parts = match part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    };
+    let results = match model {
+        Some(model) => day.run_model(
+            &Source::resolve(day.number, Some(&model)),
+            &parts,
+            &settings,
+        )?,
+        None => day.run(
+            &Source::resolve(day.number, input.as_deref()),
+            &parts,
+            &settings,
+        )?,
+    };
+    output::write(&mut std::io::stdout().lock(), format, day.number, &results)?;
+    Ok(())
+}
+
+fn parse(day: u8, input: Option<PathBuf>, output: Option<PathBuf>) -> common::Result<()> {
+    let day = days::find(day).ok_or_else(|| format!("unknown day {}", day))?;
+    let source = Source::resolve(day.number, input.as_deref());
+
+    let json = day.model(&source)?;
+    match output {
+        Some(path) => std::fs::write(&path, json + "\n")
+            .map_err(|e| format!("failed to write {}: {}", path.display(), e))?,
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+fn params(day: u8, settings: Settings) -> common::Result<()> {
+    let day = days::find(day).ok_or_else(|| format!("unknown day {}", day))?;
+    print!("{}", day.params(&settings)?);
+    Ok(())
+}
+
+fn generate(
+    day: u8,
+    seed: u64,
+    size: Option<usize>,
+    validity: Validity,
+    output: Option<PathBuf>,
+) -> common::Result<()> {
+    let day = days::find(day).ok_or_else(|| format!("unknown day {}", day))?;
+    let generated = day.generate(seed, size, validity)?;
+
+    let dir = match output {
+        Some(dir) => dir.join(format!("day{:02}", day.number)),
+        None => {
+            print!("{}", generated.input);
+            return Ok(());
+        }
+    };
+    let write = |path: PathBuf, contents: String| {
+        std::fs::write(&path, contents)
+            .map_err(|e| format!("failed to write {}: {}", path.display(), e))
+    };
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("failed to create {}: {}", dir.display(), e))?;
+    write(dir.join("input.txt"), generated.input)?;
+
+    // only the answers known by construction; verify reports the rest missing
+    let mut answers = format!("# generated with seed {}\n", seed);
+    for (part, answer) in [(1, &generated.part1), (2, &generated.part2)] {
+        if let Some(answer) = answer {
+            answers += &format!("part{}: {}\n", part, answer);
+        }
+    }
+    write(dir.join("answers.txt"), answers)?;
+    println!("Day {}: input written to {}", day.number, dir.display());
+    Ok(())
+}
+
+fn animate(
+    day: u8,
+    part: u8,
+    input: Option<PathBuf>,
+    format: render::Format,
+    output: PathBuf,
+    style: render::Style,
+    settings: Settings,
+) -> common::Result<()> {
+    let day = days::find(day).ok_or_else(|| format!("unknown day {}", day))?;
+    let source = Source::resolve(day.number, input.as_deref());
+
+    let frames = day.frames(&source, part, &settings)?;
+    let written = render::export(&frames, format, &output, style)?;
+    println!(
+        "Day {} part {}: {} frames written to {}",
+        day.number,
+        part,
+        frames.len(),
+        match written.as_slice() {
+            [file] => file.display(),
+            _ => output.display(),
+        }
+    );
+    Ok(())
+}
+
+fn watch(
+    day: u8,
+    part: u8,
+    input: Option<PathBuf>,
+    delay: Duration,
+    settings: Settings,
+) -> common::Result<()> {
+    let day = days::find(day).ok_or_else(|| format!("unknown day {}", day))?;
+    let source = Source::resolve(day.number, input.as_deref());
+
+    let mut simulation = day.simulation(&source, part, &settings)?;
+    let title = format!("Day {} part {}", day.number, part);
+    viewer::watch(simulation.as_mut(), &title, delay)
+}
+
+fn all(threads: Option<u64>) -> common::Result<()> {
+    let threads = match threads {
+        Some(t) => t as usize,
+        None => std::thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+    let selected = select_days(None)?;
+
+    let start = std::time::Instant::now();
+    let outcomes = parallel::run_days(&selected, threads);
+    let wall = start.elapsed();
+
+    println!(
+        "{:>3} {:>18} {:>18} {:>10}  status",
+        "day", "part 1", "part 2", "time (ms)"
+    );
+    let mut failed = 0;
+    for o in outcomes.iter() {
+        let elapsed_ms = o.elapsed.as_secs_f64() * 1e3;
+        match &o.result {
+            Ok(parts) => {
+                let answer = |i: usize| parts.get(i).map_or("", |p| p.answer.as_str());
+                println!(
+                    "{:>3} {:>18} {:>18} {:>10.1}  ok",
+                    o.day,
+                    answer(0),
+                    answer(1),
+                    elapsed_ms
+                );
+            }
+            Err(e) => {
+                failed += 1;
+                println!(
+                    "{:>3} {:>18} {:>18} {:>10.1}  error: {}",
+                    o.day, "-", "-", elapsed_ms, e
+                );
+            }
+        }
+    }
+    println!(
+        "{} solved, {} failed in {:.1} ms (threads: {})",
+        outcomes.len() - failed,
+        failed,
+        wall.as_secs_f64() * 1e3,
+        threads
+    );
+
+    if failed > 0 {
+        return Err(format!("failed days: {}", failed).into());
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct Tally {
+    passed: usize,
+    failed: usize,
+    missing: usize,
+}
+
+fn verify_day(day: &days::Day, tally: &mut Tally) {
+    let parts = [1, 2];
+    let report = |part: u8, status: &str| println!("Day {} part {}: {}", day.number, part, status);
+
+    let outcome = Answers::load(&day.answers_file()).and_then(|answers| {
+        let source = Source::resolve(day.number, None);
+        Ok((answers, day.run(&source, &parts, &Settings::default())?))
+    });
+    let (expected, actual) = match outcome {
+        Ok(a) => a,
+        Err(e) => {
+            // nothing to compare, so every part counts as a failure
+            for &part in parts.iter() {
+                report(part, &format!("FAIL ({})", e));
+                tally.failed += 1;
+            }
+            return;
+        }
+    };
+
+    for (&part, actual) in parts.iter().zip(actual) {
+        let actual = actual.answer;
+        match expected.expected(part) {
+            Some(expected) if expected == actual => {
+                report(part, "pass");
+                tally.passed += 1;
+            }
+            Some(expected) => {
+                report(
+                    part,
+                    &format!("FAIL (expected {}, got {})", expected, actual),
+                );
+                tally.failed += 1;
+            }
+            None => {
+                report(part, &format!("missing (got {})", actual));
+                tally.missing += 1;
+            }
+        }
+    }
+}
+
+fn select_days(day: Option<u8>) -> common::Result<Vec<&'static days::Day>> {
+    match day {
+        Some(number) => Ok(vec![
+            days::find(number).ok_or_else(|| format!("unknown day {}", number))?
+        ]),
+        None => Ok(days::DAYS.iter().collect()),
+    }
+}
+
+fn verify(day: Option<u8>) -> common::Result<()> {
+    let selected = select_days(day)?;
+
+    let mut tally = Tally::default();
+    for day in selected {
+        verify_day(day, &mut tally);
+    }
+    println!(
+        "{} passed, {} failed, {} missing",
+        tally.passed, tally.failed, tally.missing
+    );
+
+    if tally.failed > 0 {
+        return Err(format!("mismatched answers: {}", tally.failed).into());
+    }
+    Ok(())
+}
+
+fn bench(
+    day: Option<u8>,
+    iterations: usize,
+    output: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    tolerance: f64,
+) -> common::Result<()> {
+    // read the baseline up front, so a bad path fails before the long part
+    let baseline: Option<Report> = match baseline {
+        Some(path) => {
+            let json = std::fs::read_to_string(&path)
+                .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+            Some(serde_json::from_str(&json)?)
+        }
+        None => None,
+    };
+
+    let mut report = Report {
+        iterations,
+        days: Vec::new(),
+    };
+    for day in select_days(day)? {
+        let source = Source::resolve(day.number, None);
+        report
+            .days
+            .push(day.time(&source, iterations, &Settings::default())?);
+    }
+
+    println!(
+        "{:>3} {:>12} {:>12} {:>12}",
+        "day", "parse (ms)", "part1 (ms)", "part2 (ms)"
+    );
+    for t in report.days.iter() {
+        println!(
+            "{:>3} {:>12.3} {:>12.3} {:>12.3}",
+            t.day, t.parse.median_ms, t.part1.median_ms, t.part2.median_ms
+        );
+    }
+
+    if let Some(path) = output {
+        std::fs::write(&path, serde_json::to_string_pretty(&report)?)
+            .map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+    }
+
+    if let Some(baseline) = baseline {
+        let regressions = bench::regressions(&baseline, &report, tolerance);
+        for r in regressions.iter() {
+            println!(
+                "Regression: day {} {} took {:.3} ms, baseline {:.3} ms",
+                r.day, r.phase, r.current_ms, r.baseline_ms
+            );
+        }
+        if !regressions.is_empty() {
+            return Err(format!("regressed phases: {}", regressions.len()).into());
+        }
+    }
+    Ok(())
+}
+
+fn main() -> common::Result<()> {
+    let cli = Cli::parse();
+    match cli.log {
+        Some(verbosity) => logging::init(verbosity),
+        None => logging::init_from_env()?,
+    }
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            model,
+            format,
+            params,
+        } => run(day, part, input, model, format, params.into()),
+        Command::Params { day, params } => self::params(day, params.into()),
+        Command::Parse { day, input, output } => parse(day, input, output),
+        Command::Animate {
+            day,
+            part,
+            input,
+            format,
+            output,
+            scale,
+            delay,
+            params,
+        } => {
+            // GIF delays are in hundredths of a second; rounding down would
+            // make anything under 10ms no delay at all
+            let style = render::Style {
+                scale,
+                delay: delay.div_ceil(10),
+            };
+            animate(day, part, input, format, output, style, params.into())
+        }
+        Command::Watch {
+            day,
+            part,
+            input,
+            delay,
+            params,
+        } => watch(
+            day,
+            part,
+            input,
+            Duration::from_millis(delay),
+            params.into(),
+        ),
+        Command::Generate {
+            day,
+            seed,
+            size,
+            invalid,
+            output,
+        } => {
+            let validity = match invalid {
+                true => Validity::Invalid,
+                false => Validity::Valid,
+            };
+            generate(day, seed, size.map(|s| s as usize), validity, output)
+        }
+        Command::All { threads } => all(threads),
+        Command::Verify { day } => verify(day),
+        Command::Bench {
+            day,
+            iterations,
+            output,
+            baseline,
+            tolerance,
+        } => bench(day, iterations as usize, output, baseline, tolerance),
+    }
 }
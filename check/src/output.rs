@@ -0,0 +1,130 @@
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use common::Metadata;
+use serde::Serialize;
+
+use crate::days::PartResult;
+
+/// How `run` prints its results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Format {
+    /// One `Day N part P: answer` line per part
+    #[default]
+    Text,
+    /// A single JSON array of records
+    Json,
+    /// One JSON record per line
+    Ndjson,
+}
+
+// one solved part, as written by the json and ndjson formats
+#[derive(Debug, Serialize)]
+pub struct Record<'a> {
+    pub day: u8,
+    pub part: u8,
+    pub answer: &'a str,
+    pub elapsed_ms: f64,
+    #[serde(skip_serializing_if = "Metadata::is_empty")]
+    pub metadata: &'a Metadata,
+}
+
+impl<'a> Record<'a> {
+    pub fn new(day: u8, result: &'a PartResult) -> Self {
+        Record {
+            day,
+            part: result.part,
+            answer: &result.answer,
+            elapsed_ms: result.elapsed.as_secs_f64() * 1e3,
+            metadata: &result.metadata,
+        }
+    }
+}
+
+pub fn write(
+    out: &mut impl Write,
+    format: Format,
+    day: u8,
+    results: &[PartResult],
+) -> io::Result<()> {
+    let records: Vec<Record> = results.iter().map(|r| Record::new(day, r)).collect();
+    match format {
+        Format::Text => {
+            for r in records.iter() {
+                writeln!(out, "Day {} part {}: {}", r.day, r.part, r.answer)?;
+            }
+        }
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut *out, &records)?;
+            writeln!(out)?;
+        }
+        Format::Ndjson => {
+            for r in records.iter() {
+                serde_json::to_writer(&mut *out, r)?;
+                writeln!(out)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn results() -> Vec<PartResult> {
+        let mut metadata = Metadata::new();
+        metadata.insert("starts".to_string(), 1068781.into());
+        vec![
+            PartResult {
+                part: 1,
+                answer: "295".to_string(),
+                elapsed: Duration::from_micros(1500),
+                metadata: Metadata::new(),
+            },
+            PartResult {
+                part: 2,
+                answer: "1068781".to_string(),
+                elapsed: Duration::from_millis(2),
+                metadata,
+            },
+        ]
+    }
+
+    fn written(format: Format) -> String {
+        let mut out = Vec::new();
+        write(&mut out, format, 13, &results()).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn text_is_one_line_per_part() {
+        assert_eq!(
+            written(Format::Text),
+            "Day 13 part 1: 295\nDay 13 part 2: 1068781\n"
+        );
+    }
+
+    #[test]
+    fn ndjson_omits_empty_metadata() {
+        let text = written(Format::Ndjson);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                r#"{"day":13,"part":1,"answer":"295","elapsed_ms":1.5}"#,
+                r#"{"day":13,"part":2,"answer":"1068781","elapsed_ms":2.0,"metadata":{"starts":1068781}}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn json_is_an_array_of_records() {
+        let value: serde_json::Value = serde_json::from_str(&written(Format::Json)).unwrap();
+        let records = value.as_array().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1]["metadata"]["starts"], 1068781);
+    }
+}
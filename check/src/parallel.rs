@@ -0,0 +1,119 @@
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use common::{input::Source, params::Settings};
+
+use crate::days::{Day, PartResult};
+
+// what happened when a day was run: its answers, or why it has none
+pub struct Outcome {
+    pub day: u8,
+    pub elapsed: Duration,
+    pub result: Result<Vec<PartResult>, String>,
+}
+
+// run both parts of every day on a pool of worker threads; a day that fails
+// or panics is reported in its outcome and doesn't affect the others
+pub fn run_days(days: &[&Day], threads: usize) -> Vec<Outcome> {
+    map_parallel(days, threads, |day| {
+        let start = Instant::now();
+        let source = Source::resolve(day.number, None);
+        let result = caught(|| day.run(&source, &[1, 2], &Settings::default()));
+        Outcome {
+            day: day.number,
+            elapsed: start.elapsed(),
+            result,
+        }
+    })
+}
+
+// apply `f` to each item using up to `threads` workers, which take the next
+// unclaimed item as they become free; results come back in item order
+fn map_parallel<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, items.len().max(1)) {
+            let tx = tx.clone();
+            let (next, f) = (&next, &f);
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                match items.get(i) {
+                    Some(item) => tx.send((i, f(item))).expect("receiver outlives workers"),
+                    None => break,
+                }
+            });
+        }
+    });
+    drop(tx);
+
+    let mut results: Vec<(usize, R)> = rx.into_iter().collect();
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, r)| r).collect()
+}
+
+// run `f`, turning both its error and any panic into a message
+fn caught<R>(f: impl FnOnce() -> common::Result<R>) -> Result<R, String> {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(payload) => Err(format!("panicked: {}", panic_message(payload.as_ref()))),
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "unknown cause"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_parallel_keeps_item_order() {
+        let items: Vec<u64> = (0..50).collect();
+        let squares = map_parallel(&items, 4, |&i| {
+            // later items finish first
+            thread::sleep(Duration::from_micros(50 - i));
+            i * i
+        });
+        assert_eq!(squares, items.iter().map(|i| i * i).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn map_parallel_handles_more_threads_than_items() {
+        assert_eq!(map_parallel(&[1, 2], 16, |&i| i + 1), vec![2, 3]);
+        assert!(map_parallel(&[] as &[i32], 4, |&i| i).is_empty());
+    }
+
+    #[test]
+    fn caught_reports_errors_and_panics() {
+        assert_eq!(caught(|| Ok(3)), Ok(3));
+        assert_eq!(
+            caught::<()>(|| Err("missing input".into())),
+            Err("missing input".to_string())
+        );
+        assert_eq!(
+            caught::<()>(|| panic!("bad tile {}", 7)),
+            Err("panicked: bad tile 7".to_string())
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use std::{collections::BTreeMap, fmt};
+
+use serde_json::Value;
+
+/// Extra facts about how an answer was reached, keyed by name; reported
+/// alongside the answer in structured output.
+pub type Metadata = BTreeMap<String, Value>;
+
+/// A part's answer: anything displayable, optionally with metadata.
+pub trait Answer: fmt::Display {
+    fn metadata(&self) -> Metadata {
+        Metadata::new()
+    }
+}
+
+macro_rules! plain_answer {
+    ($($t:ty),*) => {
+        $(impl Answer for $t {})*
+    };
+}
+
+plain_answer!(i32, i64, u64, usize, String, &'static str);
+
+/// An answer with metadata attached.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotated<T> {
+    pub value: T,
+    pub metadata: Metadata,
+}
+
+impl<T> Annotated<T> {
+    pub fn new(value: T) -> Self {
+        Annotated {
+            value,
+            metadata: Metadata::new(),
+        }
+    }
+
+    pub fn with(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.metadata.insert(key.to_string(), value.into());
+        self
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Annotated<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<T: fmt::Display> Answer for Annotated<T> {
+    fn metadata(&self) -> Metadata {
+        self.metadata.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_answers_have_no_metadata() {
+        assert!(42i64.metadata().is_empty());
+    }
+
+    #[test]
+    fn annotated_displays_value_and_keeps_metadata() {
+        let answer = Annotated::new(3789i64).with("ids", vec![1, 2, 3]);
+        assert_eq!("3789", answer.to_string());
+        assert_eq!(
+            Some(&serde_json::json!([1, 2, 3])),
+            answer.metadata().get("ids")
+        );
+    }
+}
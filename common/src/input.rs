@@ -0,0 +1,146 @@
+use std::{
+    env,
+    error::Error,
+    ffi::OsString,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use crate::{parse::ParseError, Result, Solution};
+
+/// Environment variable naming a directory laid out like this repository,
+/// i.e. with each day's puzzle input at `dayNN/input.txt`.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where a day's puzzle input comes from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    /// An explicit path wins, with `-` meaning stdin; otherwise the day's
+    /// `input.txt` under the input directory.
+    pub fn resolve(day: u8, explicit: Option<&Path>) -> Self {
+        let input_dir = env::var_os(INPUT_DIR_VAR).filter(|d| !d.is_empty());
+        Self::resolve_in(day, explicit, input_dir)
+    }
+
+    fn resolve_in(day: u8, explicit: Option<&Path>, input_dir: Option<OsString>) -> Self {
+        match explicit {
+            Some(path) if path == Path::new("-") => Source::Stdin,
+            Some(path) => Source::File(path.to_path_buf()),
+            None => Source::File(day_dir_in(day, input_dir).join("input.txt")),
+        }
+    }
+
+    pub fn read(&self) -> Result<String> {
+        match self {
+            Source::Stdin => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| format!("failed to read stdin: {}", e))?;
+                Ok(input)
+            }
+            Source::File(path) => std::fs::read_to_string(path)
+                .map_err(|e| format!("failed to read {}: {}", path.display(), e).into()),
+        }
+    }
+
+    /// Points a parse error at this source; other errors pass through.
+    pub fn locate(&self, error: Box<dyn Error + Send + Sync>) -> Box<dyn Error + Send + Sync> {
+        match error.downcast::<ParseError>() {
+            Ok(e) => Box::new(e.in_file(self.name())),
+            Err(e) => e,
+        }
+    }
+
+    fn name(&self) -> PathBuf {
+        match self {
+            Source::Stdin => PathBuf::from("<stdin>"),
+            Source::File(path) => path.clone(),
+        }
+    }
+}
+
+/// The directory holding a day's input, under `AOC_INPUT_DIR` if it is set,
+/// or this repository otherwise.
+pub fn day_dir(day: u8) -> PathBuf {
+    day_dir_in(day, env::var_os(INPUT_DIR_VAR).filter(|d| !d.is_empty()))
+}
+
+fn day_dir_in(day: u8, input_dir: Option<OsString>) -> PathBuf {
+    let root = match input_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join(".."),
+    };
+    root.join(format!("day{:02}", day))
+}
+
+/// Where a day's binary reads its input: the optional path is the first
+/// command line argument that isn't a `name=value` parameter or one of the
+/// binary's own `--` options.
+pub fn source_from_args(day: u8) -> Source {
+    let explicit = env::args_os()
+        .skip(1)
+        .find(|a| {
+            let a = a.to_string_lossy();
+            !a.contains('=') && !a.starts_with("--")
+        })
+        .map(PathBuf::from);
+    Source::resolve(day, explicit.as_deref())
+}
+
+/// Reads and parses a day's input for its binary.
+pub fn parse_from_args<S: Solution>(day: u8) -> Result<S::Input> {
+    let source = source_from_args(day);
+    let input = source.read()?;
+    S::parse(&input).map_err(|e| source.locate(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explicit_path_wins() {
+        let source = Source::resolve_in(3, Some(Path::new("mine.txt")), Some("/data".into()));
+        assert_eq!(Source::File(PathBuf::from("mine.txt")), source);
+    }
+
+    #[test]
+    fn dash_is_stdin() {
+        assert_eq!(
+            Source::Stdin,
+            Source::resolve_in(3, Some(Path::new("-")), None)
+        );
+    }
+
+    #[test]
+    fn input_dir_used_when_set() {
+        let source = Source::resolve_in(3, None, Some("/data".into()));
+        assert_eq!(Source::File(PathBuf::from("/data/day03/input.txt")), source);
+    }
+
+    #[test]
+    fn parse_errors_name_the_source() {
+        let error: Box<dyn Error + Send + Sync> = Box::new(ParseError::at("x", "x", "a number"));
+        let located = Source::File(PathBuf::from("day01/input.txt")).locate(error);
+        assert_eq!(
+            located.to_string(),
+            "day01/input.txt:1:1: expected a number, found \"x\""
+        );
+
+        let other: Box<dyn Error + Send + Sync> = "no pair".into();
+        assert_eq!(Source::Stdin.locate(other).to_string(), "no pair");
+    }
+
+    #[test]
+    fn defaults_to_repository() {
+        let source = Source::resolve_in(16, None, None);
+        let expected = Path::new(env!("CARGO_MANIFEST_DIR")).join("../day16/input.txt");
+        assert_eq!(Source::File(expected), source);
+    }
+}
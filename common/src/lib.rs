@@ -0,0 +1,48 @@
+use std::error::Error;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+pub mod answer;
+pub mod input;
+pub mod logging;
+pub mod params;
+pub mod parse;
+
+pub use answer::{Annotated, Answer, Metadata};
+pub use parse::ParseError;
+
+/// Error type shared by all the solvers. Each day is free to use `anyhow`,
+/// `eyre` or plain boxed errors internally; all of them convert into this.
+pub type Result<T> = std::result::Result<T, Box<dyn Error + Send + Sync>>;
+
+/// A day's puzzle: parse the input once, then answer both parts from it.
+/// The parsed input can be saved as JSON and loaded back in place of the
+/// text.
+pub trait Solution {
+    type Input: Serialize + DeserializeOwned;
+    /// Puzzle constants that can be changed (see [`params`]), or `()` for a
+    /// day with none.
+    type Params: Default + Serialize + DeserializeOwned + 'static;
+    type Part1: Answer;
+    type Part2: Answer;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Part1>;
+    fn part2(input: &Self::Input) -> Result<Self::Part2>;
+
+    /// Checks an input that didn't come from `parse`, like a model read back
+    /// from JSON, for anything `parse` would have rejected.
+    fn validate(_input: &Self::Input) -> Result<()> {
+        Ok(())
+    }
+
+    /// Part 1 with other puzzle constants; `part1` is this with the defaults.
+    fn part1_with(input: &Self::Input, _params: &Self::Params) -> Result<Self::Part1> {
+        Self::part1(input)
+    }
+
+    /// Part 2 with other puzzle constants; `part2` is this with the defaults.
+    fn part2_with(input: &Self::Input, _params: &Self::Params) -> Result<Self::Part2> {
+        Self::part2(input)
+    }
+}
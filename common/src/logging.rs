@@ -0,0 +1,136 @@
+use std::{fmt, str::FromStr};
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Environment variable holding the default verbosity: one of `quiet`,
+/// `info`, `debug` or `trace`.
+pub const LOG_VAR: &str = "AOC_LOG";
+
+/// How much diagnostic output the solvers write to stderr. Answers always go
+/// to stdout regardless.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Warnings only.
+    #[default]
+    Quiet,
+    /// A line or two of interesting facts per part.
+    Info,
+    /// Intermediate results.
+    Debug,
+    /// Every step of every iteration; very large.
+    Trace,
+}
+
+impl Verbosity {
+    fn filter(self) -> LevelFilter {
+        match self {
+            Verbosity::Quiet => LevelFilter::Warn,
+            Verbosity::Info => LevelFilter::Info,
+            Verbosity::Debug => LevelFilter::Debug,
+            Verbosity::Trace => LevelFilter::Trace,
+        }
+    }
+}
+
+impl FromStr for Verbosity {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "quiet" => Ok(Verbosity::Quiet),
+            "info" => Ok(Verbosity::Info),
+            "debug" => Ok(Verbosity::Debug),
+            "trace" => Ok(Verbosity::Trace),
+            _ => Err(format!(
+                "unknown verbosity `{}`; expected quiet, info, debug or trace",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Verbosity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Verbosity::Quiet => "quiet",
+            Verbosity::Info => "info",
+            Verbosity::Debug => "debug",
+            Verbosity::Trace => "trace",
+        };
+        f.write_str(name)
+    }
+}
+
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if self.enabled(record.metadata()) {
+            eprintln!(
+                "[{} {}] {}",
+                record.level().as_str().to_ascii_lowercase(),
+                record.target(),
+                record.args()
+            );
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
+/// Routes the solvers' `log` output to stderr at the given verbosity. Safe to
+/// call more than once; later calls just change the level.
+pub fn init(verbosity: Verbosity) {
+    // only fails if a logger is already installed, which is fine
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(verbosity.filter());
+}
+
+/// As [`init`], with the verbosity taken from `AOC_LOG` (quiet if unset).
+pub fn init_from_env() -> crate::Result<()> {
+    let verbosity = match std::env::var(LOG_VAR) {
+        Ok(v) if !v.is_empty() => v.parse()?,
+        _ => Verbosity::default(),
+    };
+    init(verbosity);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_verbosity() {
+        assert_eq!(Ok(Verbosity::Quiet), "quiet".parse());
+        assert_eq!(Ok(Verbosity::Debug), "DEBUG".parse());
+        assert!("loud".parse::<Verbosity>().is_err());
+    }
+
+    #[test]
+    fn verbosity_round_trips() {
+        for v in [
+            Verbosity::Quiet,
+            Verbosity::Info,
+            Verbosity::Debug,
+            Verbosity::Trace,
+        ]
+        .iter()
+        {
+            assert_eq!(Ok(*v), v.to_string().parse());
+        }
+    }
+
+    #[test]
+    fn levels_are_ordered() {
+        assert!(Verbosity::Quiet < Verbosity::Info);
+        assert!(Verbosity::Debug < Verbosity::Trace);
+        assert_eq!(LevelFilter::Warn, Verbosity::Quiet.filter());
+    }
+}
@@ -0,0 +1,155 @@
+//! Puzzle constants a day lets you change, such as the sum day 1 looks for or
+//! day 9's preamble. They come from a TOML file, the day's `params.toml` next
+//! to its input unless another is named, with `name=value` settings on top;
+//! anything left out keeps the puzzle's value.
+
+use std::{
+    any::TypeId,
+    env,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use serde::de::DeserializeOwned;
+
+use crate::{input, Result, Solution};
+
+/// The file a day's parameters are read from when no other is named.
+pub const PARAMS_FILE: &str = "params.toml";
+
+/// Where a day's parameters come from.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Settings {
+    /// TOML file to read, instead of the day's own `params.toml`.
+    pub file: Option<PathBuf>,
+    /// `name=value` settings, taking precedence over the file.
+    pub set: Vec<String>,
+}
+
+impl Settings {
+    /// A day's parameters. The day's own file is optional, but a named one
+    /// must exist; a day with no parameters rejects any settings at all.
+    pub fn load<P: Default + DeserializeOwned + 'static>(&self, day: u8) -> Result<P> {
+        let table = match &self.file {
+            Some(path) => read_table(path)?,
+            None => {
+                let path = input::day_dir(day).join(PARAMS_FILE);
+                match read_table(&path) {
+                    Err(_) if !path.exists() => toml::Table::new(),
+                    table => table?,
+                }
+            }
+        };
+        self.load_from(day, table)
+    }
+
+    fn load_from<P: Default + DeserializeOwned + 'static>(
+        &self,
+        day: u8,
+        mut table: toml::Table,
+    ) -> Result<P> {
+        for setting in self.set.iter() {
+            let (name, value) = setting
+                .split_once('=')
+                .ok_or_else(|| format!("expected `name=value`, found `{}`", setting))?;
+            table.insert(name.trim().to_string(), value_of(value.trim()));
+        }
+        if table.is_empty() {
+            return Ok(P::default());
+        }
+        if TypeId::of::<P>() == TypeId::of::<()>() {
+            return Err(format!("day {} has no parameters", day).into());
+        }
+        toml::Value::Table(table)
+            .try_into()
+            .map_err(|e| format!("day {} parameters: {}", day, e.to_string().trim_end()).into())
+    }
+}
+
+fn read_table(path: &Path) -> Result<toml::Table> {
+    let text = std::fs::read_to_string(path).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => format!("no parameters file {}", path.display()),
+        _ => format!("failed to read {}: {}", path.display(), e),
+    })?;
+    toml::from_str(&text)
+        .map_err(|e| format!("{}: {}", path.display(), e.to_string().trim_end()).into())
+}
+
+// the value as TOML, or failing that as plain text, so a string needs no
+// quotes on the command line
+fn value_of(text: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("value = {}", text))
+        .ok()
+        .and_then(|mut t| t.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(text.to_string()))
+}
+
+/// The parameters for a day's binary: the day's own file, with any
+/// `name=value` command line arguments on top; the binary's own `--` options
+/// aren't parameters.
+pub fn from_args<S: Solution>(day: u8) -> Result<S::Params> {
+    let set = env::args()
+        .skip(1)
+        .filter(|a| a.contains('=') && !a.starts_with("--"))
+        .collect();
+    Settings { file: None, set }.load(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    struct Params {
+        target: i32,
+        bag: String,
+        slopes: Vec<(i64, i64)>,
+    }
+
+    impl Default for Params {
+        fn default() -> Self {
+            Params {
+                target: 2020,
+                bag: "shiny gold".into(),
+                slopes: vec![(3, 1)],
+            }
+        }
+    }
+
+    fn settings(set: &[&str]) -> Settings {
+        Settings {
+            file: None,
+            set: set.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn settings_override_the_file() {
+        let file = toml::from_str("target = 99\nslopes = [[1, 2]]").unwrap();
+        let params: Params = settings(&["target=5"]).load_from(1, file).unwrap();
+        assert_eq!(5, params.target);
+        assert_eq!("shiny gold", params.bag);
+        assert_eq!(vec![(1, 2)], params.slopes);
+    }
+
+    #[test]
+    fn strings_need_no_quotes() {
+        let load = |set| settings(&[set]).load_from::<Params>(7, toml::Table::new());
+        assert_eq!("dull red", load("bag=dull red").unwrap().bag);
+        assert_eq!("dull red", load("bag = \"dull red\"").unwrap().bag);
+    }
+
+    #[test]
+    fn mistakes_are_reported() {
+        let load = |set| settings(&[set]).load_from::<Params>(1, toml::Table::new());
+        assert!(load("target").is_err());
+        assert!(load("target=lots").is_err());
+        assert!(load("tagret=5").is_err());
+        let none = settings(&["x=1"]).load_from::<()>(2, toml::Table::new());
+        assert_eq!("day 2 has no parameters", none.unwrap_err().to_string());
+        assert!(settings(&[]).load_from::<()>(2, toml::Table::new()).is_ok());
+    }
+}
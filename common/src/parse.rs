@@ -0,0 +1,207 @@
+use std::{borrow::Cow, error::Error, fmt, path::PathBuf, str::FromStr};
+
+/// Malformed puzzle input: where it went wrong, the text found there and
+/// what was expected instead.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Set by whoever read the input, as the parsers only see its text.
+    pub file: Option<PathBuf>,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, in characters.
+    pub column: usize,
+    /// The offending text, up to the end of its line.
+    pub found: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// An error at `found`, which must be a slice of `input`; its position
+    /// in `input` gives the line and column.
+    pub fn at(input: &str, found: &str, expected: impl Into<String>) -> Self {
+        let offset = offset_in(input, found);
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        ParseError {
+            file: None,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            found: found.lines().next().unwrap_or("").to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// An error for input that stops before something that was expected.
+    pub fn at_end(input: &str, expected: impl Into<String>) -> Self {
+        Self::at(input, &input[input.len()..], expected)
+    }
+
+    /// Moves an error found while parsing `inner`, a slice of `outer`, to
+    /// the corresponding position in `outer`.
+    pub fn within(self, outer: &str, inner: &str) -> Self {
+        let start = Self::at(outer, inner, "");
+        let column = match self.line {
+            1 => start.column + self.column - 1,
+            _ => self.column,
+        };
+        ParseError {
+            line: start.line + self.line - 1,
+            column,
+            ..self
+        }
+    }
+
+    pub fn in_file(self, file: impl Into<PathBuf>) -> Self {
+        ParseError {
+            file: Some(file.into()),
+            ..self
+        }
+    }
+}
+
+// long lines are cut short when displayed
+const FOUND_DISPLAY_LEN: usize = 40;
+
+// byte offset of `inner` within `outer`; clamped to a char boundary inside
+// `outer` rather than panicking should `inner` come from somewhere else
+fn offset_in(outer: &str, inner: &str) -> usize {
+    let start = outer.as_ptr() as usize;
+    let mut offset = (inner.as_ptr() as usize)
+        .saturating_sub(start)
+        .min(outer.len());
+    while !outer.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:{}:{}: ", file.display(), self.line, self.column)?,
+            None => write!(f, "line {}, column {}: ", self.line, self.column)?,
+        }
+        write!(f, "expected {}, ", self.expected)?;
+        match self.found.char_indices().nth(FOUND_DISPLAY_LEN) {
+            _ if self.found.is_empty() => write!(f, "found nothing"),
+            Some((end, _)) => write!(f, "found \"{}...\"", &self.found[..end]),
+            None => write!(f, "found \"{}\"", self.found),
+        }
+    }
+}
+
+// the diagnostic itself, as a `main` returning this error prints it with Debug
+impl fmt::Debug for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Puzzle input as the parsers expect it: `\n` line endings, no trailing
+/// whitespace on any line and no blank lines at the end, so that files saved
+/// on Windows or by a careless editor parse the same. Lines and columns are
+/// unchanged, so errors found in the result still point at the original.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let mut lines: Vec<&str> = input.split('\n').map(str::trim_end).collect();
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+
+    let mut normal = lines.join("\n");
+    if !normal.is_empty() {
+        normal.push('\n');
+    }
+    if normal == input {
+        Cow::Borrowed(input)
+    } else {
+        Cow::Owned(normal)
+    }
+}
+
+/// Parses each line of `input` with `f`, which reports errors relative to
+/// the line it was given.
+pub fn lines<'a, T>(
+    input: &'a str,
+    mut f: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .map(|line| f(line).map_err(|e| e.within(input, line)))
+        .collect()
+}
+
+/// Parses `text`, a slice of `input`, as a number.
+pub fn number<T: FromStr>(input: &str, text: &str) -> Result<T, ParseError> {
+    text.parse()
+        .map_err(|_| ParseError::at(input, text, "a number"))
+}
+
+/// Strips `prefix` from the start of `text`, a slice of `input`.
+pub fn tag<'a>(input: &str, text: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    text.strip_prefix(prefix)
+        .ok_or_else(|| ParseError::at(input, text, format!("\"{}\"", prefix)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position_of_slice() {
+        let input = "..#\n.x#\n";
+        let e = ParseError::at(input, &input[5..], "'.' or '#'");
+        assert_eq!((e.line, e.column), (2, 2));
+        assert_eq!(e.found, "x#");
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 2: expected '.' or '#', found \"x#\""
+        );
+    }
+
+    #[test]
+    fn errors_within_lines_are_relocated() {
+        let input = "1\n2\n3 4\n";
+        let e = lines(input, |line| number::<i32>(line, line)).unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (3, 1, "3 4"));
+
+        let e = lines(input, |line| {
+            let (_, second) = line.split_at(line.len().min(2));
+            tag(line, second, "5")
+        })
+        .unwrap_err();
+        assert_eq!((e.line, e.column), (1, 2));
+    }
+
+    #[test]
+    fn normalized_line_endings_and_whitespace() {
+        assert_eq!(normalize("1 \r\n\r\n2\t\r\n \r\n\r\n"), "1\n\n2\n");
+        assert_eq!(normalize("a\nb"), "a\nb\n");
+        assert_eq!(normalize("\n  \n"), "");
+        assert!(matches!(normalize("a\n\nb\n"), Cow::Borrowed(_)));
+
+        let messy = "1\r\n2x  \r\n";
+        let e = lines(&normalize(messy), |line| number::<i32>(line, line)).unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 1, "2x"));
+    }
+
+    #[test]
+    fn long_text_is_shortened() {
+        let input = "a".repeat(50);
+        let e = ParseError::at(&input, &input, "b");
+        assert_eq!(e.found.len(), 50);
+        assert!(e
+            .to_string()
+            .ends_with(&format!("found \"{}...\"", "a".repeat(40))));
+    }
+
+    #[test]
+    fn end_of_input_and_file() {
+        let e = ParseError::at_end("ab\ncd", "a blank line").in_file("day06/input.txt");
+        assert_eq!(
+            e.to_string(),
+            "day06/input.txt:2:3: expected a blank line, found nothing"
+        );
+    }
+}
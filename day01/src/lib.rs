@@ -0,0 +1,254 @@
+use common::{parse, Result, Solution};
+use serde::{Deserialize, Serialize};
+
+pub mod subsets;
+
+/// The sum the puzzle looks for.
+pub const TARGET: i32 = 2020;
+
+/// The puzzle constants, as in the puzzle unless changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    /// The sum to look for.
+    pub target: i32,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { target: TARGET }
+    }
+}
+
+/// Every combination of `k` entries that sum to `target`, as their indices
+/// in ascending order, the combinations sorted. Entries with equal values are
+/// still different lines, so each way of picking among them counts.
+///
+/// Sorts the entries once, then fixes all but the last two and finds those
+/// with two pointers closing in from either end: O(n^(k-1)) plus the size of
+/// the output, rather than O(n^k).
+pub fn k_sums(list: &[i32], k: usize, target: i64) -> Vec<Vec<usize>> {
+    let mut order: Vec<usize> = (0..list.len()).collect();
+    order.sort_by_key(|&i| list[i]);
+    let values: Vec<i64> = order.iter().map(|&i| list[i] as i64).collect();
+
+    let mut found = Vec::new();
+    sums_from(
+        &values,
+        0,
+        k,
+        target,
+        &mut Vec::with_capacity(k),
+        &mut found,
+    );
+
+    let mut tuples: Vec<Vec<usize>> = found
+        .into_iter()
+        .map(|positions| {
+            let mut tuple: Vec<usize> = positions.iter().map(|&p| order[p]).collect();
+            tuple.sort_unstable();
+            tuple
+        })
+        .collect();
+    tuples.sort_unstable();
+    tuples
+}
+
+// adds to `found` every way of extending `chosen` with `k` more positions
+// from `start` on whose values sum to `target`; `values` is sorted
+fn sums_from(
+    values: &[i64],
+    start: usize,
+    k: usize,
+    target: i64,
+    chosen: &mut Vec<usize>,
+    found: &mut Vec<Vec<usize>>,
+) {
+    let mut found_with = |extra: &[usize]| {
+        let mut tuple = chosen.clone();
+        tuple.extend_from_slice(extra);
+        found.push(tuple);
+    };
+    match k {
+        0 => {
+            if target == 0 {
+                found_with(&[]);
+            }
+        }
+        1 => {
+            let first = start + values[start..].partition_point(|&v| v < target);
+            let end = start + values[start..].partition_point(|&v| v <= target);
+            for p in first..end {
+                found_with(&[p]);
+            }
+        }
+        2 => {
+            let (mut lo, mut hi) = (start, values.len().saturating_sub(1));
+            while lo < hi {
+                let sum = values[lo] + values[hi];
+                if sum < target {
+                    lo += 1;
+                } else if sum > target {
+                    hi -= 1;
+                } else if values[lo] == values[hi] {
+                    // everything between is the same value, so any two will do
+                    for a in lo..hi {
+                        for b in a + 1..=hi {
+                            found_with(&[a, b]);
+                        }
+                    }
+                    break;
+                } else {
+                    // every entry of the low value with every one of the high
+                    let lo_end = lo + values[lo..].partition_point(|&v| v == values[lo]);
+                    let hi_start = values[..=hi].partition_point(|&v| v < values[hi]);
+                    for a in lo..lo_end {
+                        for b in hi_start..=hi {
+                            found_with(&[a, b]);
+                        }
+                    }
+                    lo = lo_end;
+                    hi = hi_start - 1;
+                }
+            }
+        }
+        _ => {
+            for p in start..values.len() {
+                let rest = &values[p + 1..];
+                if rest.len() < k - 1 {
+                    break;
+                }
+                // the smallest sum from here on only grows, the largest may
+                // still be short of the target
+                let smallest: i64 = values[p] + rest[..k - 1].iter().sum::<i64>();
+                let largest: i64 = values[p] + rest[rest.len() - (k - 1)..].iter().sum::<i64>();
+                if smallest > target {
+                    break;
+                }
+                if largest < target {
+                    continue;
+                }
+                chosen.push(p);
+                sums_from(values, p + 1, k - 1, target - values[p], chosen, found);
+                chosen.pop();
+            }
+        }
+    }
+}
+
+/// The first pair of entries, in list order, that sum to `target`.
+pub fn find_pair(list: &[i32], target: i32) -> Option<(i32, i32)> {
+    let sums = k_sums(list, 2, target as i64);
+    sums.first().map(|t| (list[t[0]], list[t[1]]))
+}
+
+/// The first triple of entries, in list order, that sum to `target`.
+pub fn find_triple(list: &[i32], target: i32) -> Option<(i32, i32, i32)> {
+    let sums = k_sums(list, 3, target as i64);
+    sums.first().map(|t| (list[t[0]], list[t[1]], list[t[2]]))
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<i32>;
+    type Params = Params;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<i32>> {
+        let input = &parse::normalize(input);
+        Ok(parse::lines(input, |l| parse::number(l, l))?)
+    }
+
+    fn part1(list: &Vec<i32>) -> Result<i32> {
+        Self::part1_with(list, &Params::default())
+    }
+
+    fn part2(list: &Vec<i32>) -> Result<i32> {
+        Self::part2_with(list, &Params::default())
+    }
+
+    // part 1 -- product of the two entries that sum to the target, 2020
+    fn part1_with(list: &Vec<i32>, params: &Params) -> Result<i32> {
+        let (vi, vj) = find_pair(list, params.target)
+            .ok_or_else(|| format!("no pair of entries sums to {}", params.target))?;
+        Ok(vi * vj)
+    }
+
+    // part 2 -- product of the three entries that sum to the target, 2020
+    fn part2_with(list: &Vec<i32>, params: &Params) -> Result<i32> {
+        let (vi, vj, vk) = find_triple(list, params.target)
+            .ok_or_else(|| format!("no triple of entries sums to {}", params.target))?;
+        Ok(vi * vj * vk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: [i32; 6] = [1721, 979, 366, 299, 675, 1456];
+
+    // every combination of `k` indices, tried one by one
+    fn k_sums_by_brute_force(list: &[i32], k: usize, target: i64) -> Vec<Vec<usize>> {
+        fn extend(
+            list: &[i32],
+            k: usize,
+            target: i64,
+            tuple: &mut Vec<usize>,
+            out: &mut Vec<Vec<usize>>,
+        ) {
+            if tuple.len() == k {
+                let sum: i64 = tuple.iter().map(|&i| list[i] as i64).sum();
+                if sum == target {
+                    out.push(tuple.clone());
+                }
+                return;
+            }
+            let start = tuple.last().map_or(0, |&i| i + 1);
+            for i in start..list.len() {
+                tuple.push(i);
+                extend(list, k, target, tuple, out);
+                tuple.pop();
+            }
+        }
+        let mut out = Vec::new();
+        extend(list, k, target, &mut Vec::new(), &mut out);
+        out
+    }
+
+    #[test]
+    fn example_pair_and_triple() {
+        assert_eq!(Some((1721, 299)), find_pair(&EXAMPLE, 2020));
+        assert_eq!(Some((979, 366, 675)), find_triple(&EXAMPLE, 2020));
+        assert_eq!(vec![vec![0, 3]], k_sums(&EXAMPLE, 2, 2020));
+    }
+
+    #[test]
+    fn equal_entries_are_different_lines() {
+        assert_eq!(
+            vec![vec![0, 1], vec![0, 3], vec![1, 3]],
+            k_sums(&[1010, 1010, 7, 1010], 2, 2020)
+        );
+        assert_eq!(
+            vec![vec![0, 2], vec![0, 3], vec![1, 2], vec![1, 3]],
+            k_sums(&[1, 1, 2, 2], 2, 3)
+        );
+        assert_eq!(vec![vec![1], vec![2]], k_sums(&[3, 5, 5], 1, 5));
+        assert_eq!(vec![Vec::<usize>::new()], k_sums(&[3, 5], 0, 0));
+        assert!(k_sums(&[1010], 2, 2020).is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn k_sums_match_brute_force(
+            list in prop::collection::vec(-20..20, 0..12),
+            k in 0usize..6,
+            target in -40i64..40,
+        ) {
+            prop_assert_eq!(k_sums_by_brute_force(&list, k, target), k_sums(&list, k, target));
+        }
+    }
+}
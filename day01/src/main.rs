@@ -1,56 +1,46 @@
-use std::{
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use common::Solution;
+use day01::{subsets::SubsetSums, Day01};
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let file = File::open("input.txt")?;
-    let buffered = BufReader::new(file);
-    let list: Vec<i32> = buffered
-        .lines()
-        .map(|l| l.unwrap().parse().unwrap())
-        .collect();
+// sets listed by --subsets unless --limit=N says otherwise
+const DEFAULT_LIMIT: usize = 20;
 
-    // part 1
-    for i in 0..list.len() - 1 {
-        for j in i + 1..list.len() {
-            let vi = list[i];
-            let vj = list[j];
+fn main() -> common::Result<()> {
+    common::logging::init_from_env()?;
+    let list = common::input::parse_from_args::<Day01>(1)?;
+    let params = common::params::from_args::<Day01>(1)?;
 
-            if vi + vj == 2020 {
-                println!(
-                    "part1 -> vi = {}, vj = {}, vi + vj = {}, vi * vj = {}",
-                    vi,
-                    vj,
-                    vi + vj,
-                    vi * vj
-                );
-            }
-        }
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|a| a == "--subsets") {
+        let limit = match args.iter().find_map(|a| a.strip_prefix("--limit=")) {
+            Some(n) => n.parse().map_err(|e| format!("bad --limit {}: {}", n, e))?,
+            None => DEFAULT_LIMIT,
+        };
+        return print_subsets(&list, params.target, limit);
     }
 
-    // part 2
-    for i in 0..list.len() - 2 {
-        for j in i + 1..list.len() - 1 {
-            for k in j + 1..list.len() {
-                let vi = list[i];
-                let vj = list[j];
-                let vk = list[k];
+    println!("part1 -> product = {}", Day01::part1_with(&list, &params)?);
+    println!("part2 -> product = {}", Day01::part2_with(&list, &params)?);
 
-                if vi + vj + vk == 2020 {
-                    println!(
-                        "part2 -> vi = {}, vj = {}, vk = {}, sum = {}, product = {}",
-                        vi,
-                        vj,
-                        vk,
-                        vi + vj + vk,
-                        vi * vj * vk
-                    );
-                }
-            }
-        }
-    }
+    Ok(())
+}
 
+/// Every set of entries, of any size, summing to the target; only the first
+/// `limit` are listed.
+fn print_subsets(list: &[i32], target: i32, limit: usize) -> common::Result<()> {
+    let sums = SubsetSums::new(list, target as i64)?;
+    println!(
+        "subsets -> {} sets sum to {} (by {:?})",
+        sums.count(),
+        target,
+        sums.method()
+    );
+    for set in sums.iter().take(limit) {
+        let lines: Vec<String> = set.iter().map(|i| (i + 1).to_string()).collect();
+        let values: Vec<String> = set.iter().map(|&i| list[i].to_string()).collect();
+        println!("lines {}: {}", lines.join(", "), values.join(" + "));
+    }
+    if sums.count() > limit as u128 {
+        println!("... and {} more", sums.count() - limit as u128);
+    }
     Ok(())
 }
@@ -0,0 +1,448 @@
+//! Sets of entries of any size that add up to a target.
+//!
+//! Two ways of finding them, picked by the size of the problem:
+//! - a table of the sums reachable with each prefix of the list, when the
+//!   entries' sums span a small enough range; pseudo-polynomial, so it copes
+//!   with long lists of modest numbers like the puzzle's
+//! - meeting in the middle, when they don't: every sum of each half of the
+//!   list, matched up through a sorted copy of one side; exponential, but
+//!   only in half the length of the list
+//!
+//! Either way, the sets are counted up front and listed lazily, so a cap on
+//! the listing (`iter().take(n)`) costs no more than the sets it returns.
+
+use std::ops::Range;
+
+use common::Result;
+
+// the table is a bit per reachable sum for every prefix of the list, plus a
+// count per sum
+const MAX_TABLE_BITS: u128 = 1 << 28;
+const MAX_TABLE_WIDTH: u128 = 1 << 22;
+
+// each half is listed in full, as 2^(n/2) sums
+const MAX_HALVED: usize = 40;
+
+/// How the sets are found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Table,
+    MeetInTheMiddle,
+}
+
+/// The non-empty sets of entries that sum to a target, each as the indices
+/// of its entries in ascending order. Equal entries on different lines are
+/// different sets.
+pub struct SubsetSums {
+    count: u128,
+    solver: Solver,
+}
+
+enum Solver {
+    Table(Table),
+    Halves(Halves),
+}
+
+impl SubsetSums {
+    /// The sets in `list` summing to `target`, by whichever method suits.
+    pub fn new(list: &[i32], target: i64) -> Result<Self> {
+        let method = match table_bits(list) {
+            Some(_) => Method::Table,
+            None => Method::MeetInTheMiddle,
+        };
+        Self::with_method(list, target, method)
+    }
+
+    /// The sets in `list` summing to `target`, found by `method`; fails if
+    /// the list is too big for it.
+    pub fn with_method(list: &[i32], target: i64, method: Method) -> Result<Self> {
+        let values: Vec<i64> = list.iter().map(|&v| v as i64).collect();
+        let solver = match method {
+            Method::Table => {
+                table_bits(list).ok_or_else(|| {
+                    format!(
+                        "the sums of {} entries span too wide a range for a table",
+                        list.len()
+                    )
+                })?;
+                Solver::Table(Table::new(values, target))
+            }
+            Method::MeetInTheMiddle if list.len() > MAX_HALVED => {
+                return Err(format!(
+                    "{} entries are too many to meet in the middle, at most {}",
+                    list.len(),
+                    MAX_HALVED
+                )
+                .into())
+            }
+            Method::MeetInTheMiddle => Solver::Halves(Halves::new(values, target)),
+        };
+        let count = match &solver {
+            Solver::Table(table) => table.count(),
+            Solver::Halves(halves) => halves.count(),
+        };
+        Ok(SubsetSums { count, solver })
+    }
+
+    pub fn method(&self) -> Method {
+        match self.solver {
+            Solver::Table(_) => Method::Table,
+            Solver::Halves(_) => Method::MeetInTheMiddle,
+        }
+    }
+
+    /// How many sets there are, saturating at `u128::MAX`.
+    pub fn count(&self) -> u128 {
+        self.count
+    }
+
+    /// The sets, found one at a time as they are asked for.
+    pub fn iter(&self) -> Box<dyn Iterator<Item = Vec<usize>> + '_> {
+        match &self.solver {
+            Solver::Table(table) => Box::new(table.iter()),
+            Solver::Halves(halves) => Box::new(halves.iter()),
+        }
+    }
+}
+
+// the range of sums a subset of `list` can have
+fn sum_range(list: &[i32]) -> (i64, i64) {
+    let low = list.iter().filter(|&&v| v < 0).map(|&v| v as i64).sum();
+    let high = list.iter().filter(|&&v| v > 0).map(|&v| v as i64).sum();
+    (low, high)
+}
+
+// the size of the table for `list`, if it isn't too big
+fn table_bits(list: &[i32]) -> Option<u128> {
+    let (low, high) = sum_range(list);
+    let width = (high - low) as u128 + 1;
+    let bits = width * (list.len() as u128 + 1);
+    Some(bits).filter(|&b| width <= MAX_TABLE_WIDTH && b <= MAX_TABLE_BITS)
+}
+
+// one bit per sum in the range
+#[derive(Clone)]
+struct Bits(Vec<u64>);
+
+impl Bits {
+    fn new(width: usize) -> Self {
+        Bits(vec![0; width.div_ceil(64)])
+    }
+
+    fn get(&self, i: usize) -> bool {
+        self.0[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    fn set(&mut self, i: usize) {
+        self.0[i / 64] |= 1 << (i % 64);
+    }
+}
+
+// `reachable[i]` holds the sums of subsets of the first i entries, offset by
+// the lowest possible sum
+struct Table {
+    values: Vec<i64>,
+    target: i64,
+    low: i64,
+    width: usize,
+    reachable: Vec<Bits>,
+}
+
+impl Table {
+    fn new(values: Vec<i64>, target: i64) -> Self {
+        let low: i64 = values.iter().filter(|&&v| v < 0).sum();
+        let high: i64 = values.iter().filter(|&&v| v > 0).sum();
+        let width = (high - low + 1) as usize;
+
+        let mut first = Bits::new(width);
+        first.set((-low) as usize);
+        let mut reachable = vec![first];
+        for &v in values.iter() {
+            let before = reachable.last().unwrap();
+            let mut after = before.clone();
+            for s in 0..width {
+                let from = s as i64 - v;
+                if from >= 0 && (from as usize) < width && before.get(from as usize) {
+                    after.set(s);
+                }
+            }
+            reachable.push(after);
+        }
+
+        Table {
+            values,
+            target,
+            low,
+            width,
+            reachable,
+        }
+    }
+
+    // the column of `sum`, if it is in range
+    fn column(&self, sum: i64) -> Option<usize> {
+        let column = sum - self.low;
+        Some(column as usize).filter(|_| column >= 0 && column < self.width as i64)
+    }
+
+    // counts the subsets reaching each sum, adding one entry at a time;
+    // going against the direction of the shift reads each count before it
+    // is updated
+    fn count(&self) -> u128 {
+        let target = match self.column(self.target) {
+            Some(t) => t,
+            None => return 0,
+        };
+        let mut counts = vec![0u128; self.width];
+        counts[(-self.low) as usize] = 1;
+        for &v in self.values.iter() {
+            let shift = v.unsigned_abs() as usize;
+            if v > 0 {
+                for s in (0..self.width - shift).rev() {
+                    counts[s + shift] = counts[s + shift].saturating_add(counts[s]);
+                }
+            } else if v < 0 {
+                for s in shift..self.width {
+                    counts[s - shift] = counts[s - shift].saturating_add(counts[s]);
+                }
+            } else {
+                for c in counts.iter_mut() {
+                    *c = c.saturating_mul(2);
+                }
+            }
+        }
+        // less the empty set
+        match self.target {
+            0 => counts[target] - 1,
+            _ => counts[target],
+        }
+    }
+
+    fn iter(&self) -> TableIter<'_> {
+        let stack = match self.column(self.target) {
+            Some(t) if self.reachable[self.values.len()].get(t) => {
+                vec![(self.values.len(), self.target, Vec::new())]
+            }
+            _ => Vec::new(),
+        };
+        TableIter { table: self, stack }
+    }
+}
+
+// a depth-first walk back through the table from the target; every state on
+// the stack is reachable, so every path ends in a set
+struct TableIter<'a> {
+    table: &'a Table,
+    // entries still to choose from, the sum they must make, and the indices
+    // chosen so far, highest first
+    stack: Vec<(usize, i64, Vec<usize>)>,
+}
+
+impl Iterator for TableIter<'_> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        let table = self.table;
+        let reachable =
+            |i: usize, sum: i64| table.column(sum).is_some_and(|c| table.reachable[i].get(c));
+        while let Some((i, sum, chosen)) = self.stack.pop() {
+            if i == 0 {
+                if chosen.is_empty() {
+                    continue;
+                }
+                let mut set = chosen;
+                set.reverse();
+                return Some(set);
+            }
+            let v = table.values[i - 1];
+            if reachable(i - 1, sum - v) {
+                let mut with = chosen.clone();
+                with.push(i - 1);
+                self.stack.push((i - 1, sum - v, with));
+            }
+            if reachable(i - 1, sum) {
+                self.stack.push((i - 1, sum, chosen));
+            }
+        }
+        None
+    }
+}
+
+// every subset of each half of the list, by sum; the right half's sorted so
+// the partners of a left sum are a range of it
+struct Halves {
+    split: usize,
+    target: i64,
+    left: Vec<(i64, u64)>,
+    right: Vec<(i64, u64)>,
+}
+
+// the sum of every subset of `values`, indexed by the bit mask of its members
+fn subset_sums(values: &[i64]) -> Vec<(i64, u64)> {
+    let mut sums = vec![(0, 0)];
+    for (i, &v) in values.iter().enumerate() {
+        let with: Vec<(i64, u64)> = sums.iter().map(|&(s, m)| (s + v, m | 1 << i)).collect();
+        sums.extend(with);
+    }
+    sums
+}
+
+impl Halves {
+    fn new(values: Vec<i64>, target: i64) -> Self {
+        let split = values.len() / 2;
+        let left = subset_sums(&values[..split]);
+        let mut right = subset_sums(&values[split..]);
+        right.sort_unstable();
+        Halves {
+            split,
+            target,
+            left,
+            right,
+        }
+    }
+
+    // the right subsets that complete a left subset with sum `sum`
+    fn partners(&self, sum: i64) -> Range<usize> {
+        let wanted = self.target - sum;
+        let start = self.right.partition_point(|&(s, _)| s < wanted);
+        let end = self.right.partition_point(|&(s, _)| s <= wanted);
+        start..end
+    }
+
+    fn count(&self) -> u128 {
+        let pairs: u128 = self
+            .left
+            .iter()
+            .map(|&(sum, _)| self.partners(sum).len() as u128)
+            .sum();
+        // less the empty set
+        match self.target {
+            0 => pairs - 1,
+            _ => pairs,
+        }
+    }
+
+    fn iter(&self) -> HalvesIter<'_> {
+        HalvesIter {
+            halves: self,
+            left: 0,
+            partners: self.left.first().map_or(0..0, |&(s, _)| self.partners(s)),
+        }
+    }
+}
+
+struct HalvesIter<'a> {
+    halves: &'a Halves,
+    left: usize,
+    partners: Range<usize>,
+}
+
+impl Iterator for HalvesIter<'_> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        let halves = self.halves;
+        loop {
+            match self.partners.next() {
+                Some(r) => {
+                    let (_, left) = halves.left[self.left];
+                    let (_, right) = halves.right[r];
+                    if left == 0 && right == 0 {
+                        continue;
+                    }
+                    let members = |mask: u64, offset: usize| {
+                        (0..64)
+                            .filter(move |b| mask & (1 << b) != 0)
+                            .map(move |b| b + offset)
+                    };
+                    let set = members(left, 0)
+                        .chain(members(right, halves.split))
+                        .collect();
+                    return Some(set);
+                }
+                None => {
+                    self.left += 1;
+                    let &(sum, _) = halves.left.get(self.left)?;
+                    self.partners = halves.partners(sum);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // every non-empty subset, tried one by one
+    fn by_brute_force(list: &[i32], target: i64) -> Vec<Vec<usize>> {
+        let mut sets: Vec<Vec<usize>> = (1..1u32 << list.len())
+            .map(|mask| {
+                (0..list.len())
+                    .filter(|&i| mask & (1 << i) != 0)
+                    .collect::<Vec<usize>>()
+            })
+            .filter(|set| set.iter().map(|&i| list[i] as i64).sum::<i64>() == target)
+            .collect();
+        sets.sort();
+        sets
+    }
+
+    fn sorted(sums: &SubsetSums) -> Vec<Vec<usize>> {
+        let mut sets: Vec<Vec<usize>> = sums.iter().collect();
+        sets.sort();
+        sets
+    }
+
+    #[test]
+    fn example_sets() {
+        let list = [1721, 979, 366, 299, 675, 1456];
+        for method in [Method::Table, Method::MeetInTheMiddle] {
+            let sums = SubsetSums::with_method(&list, 2020, method).unwrap();
+            assert_eq!(2, sums.count());
+            assert_eq!(vec![vec![0, 3], vec![1, 2, 4]], sorted(&sums));
+        }
+    }
+
+    #[test]
+    fn the_puzzle_input_uses_the_table() {
+        let list: Vec<i32> = (0..200).map(|i| 1000 + i).collect();
+        let sums = SubsetSums::new(&list, 2020).unwrap();
+        assert_eq!(Method::Table, sums.method());
+        assert_eq!(10, sums.count());
+    }
+
+    #[test]
+    fn wide_spread_entries_meet_in_the_middle() {
+        let list: Vec<i32> = (0..30).map(|i| 1 << i).collect();
+        let sums = SubsetSums::new(&list, (1 << 20) + 5).unwrap();
+        assert_eq!(Method::MeetInTheMiddle, sums.method());
+        assert_eq!(vec![vec![0, 2, 20]], sorted(&sums));
+
+        let too_many: Vec<i32> = (0..60).map(|i| i32::MAX - i).collect();
+        assert!(SubsetSums::new(&too_many, 0).is_err());
+    }
+
+    #[test]
+    fn counts_without_listing() {
+        let zeros = vec![0; 100];
+        let sums = SubsetSums::new(&zeros, 0).unwrap();
+        assert_eq!((1 << 100) - 1, sums.count());
+        assert_eq!(3, sums.iter().take(3).count());
+    }
+
+    proptest! {
+        #[test]
+        fn both_methods_match_brute_force(
+            list in prop::collection::vec(-20..20, 0..12),
+            target in -60i64..60,
+        ) {
+            let expected = by_brute_force(&list, target);
+            for method in [Method::Table, Method::MeetInTheMiddle] {
+                let sums = SubsetSums::with_method(&list, target, method).unwrap();
+                prop_assert_eq!(expected.len() as u128, sums.count());
+                prop_assert_eq!(&expected, &sorted(&sums));
+            }
+        }
+    }
+}
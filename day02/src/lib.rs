@@ -0,0 +1,204 @@
+use common::{parse, ParseError, Result, Solution};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+pub mod policy;
+
+lazy_static::lazy_static! {
+    static ref RE_ENTRY: Regex = Regex::new("^(\\d+)-(\\d+) ([a-z]{1}): ([a-z]+)$").unwrap();
+}
+
+/// A line of the database: a policy, and the password it applies to.
+#[derive(Serialize, Deserialize)]
+pub struct Entry {
+    pub n1: usize,
+    pub n2: usize,
+    pub ch: char,
+    pub password: String,
+}
+
+/// How a line fares against a policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Valid,
+    /// The password breaks the policy, for the reason given.
+    Invalid(String),
+    /// The policy makes no sense for the line, e.g. a position past the end
+    /// of the password.
+    Malformed(String),
+}
+
+impl Verdict {
+    pub fn is_valid(&self) -> bool {
+        *self == Verdict::Valid
+    }
+
+    /// Why the line isn't valid, if it isn't.
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            Verdict::Valid => None,
+            Verdict::Invalid(reason) | Verdict::Malformed(reason) => Some(reason),
+        }
+    }
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Valid => write!(f, "valid"),
+            Verdict::Invalid(_) => write!(f, "invalid"),
+            Verdict::Malformed(_) => write!(f, "malformed"),
+        }
+    }
+}
+
+impl Entry {
+    /// The character occurs between `n1` and `n2` times, inclusive.
+    pub fn by_count(&self) -> Verdict {
+        let (min, max) = (self.n1, self.n2);
+        if min > max {
+            return Verdict::Malformed(format!("{}-{} is an empty range", min, max));
+        }
+        let count = self.password.chars().filter(|c| *c == self.ch).count();
+        match count >= min && count <= max {
+            true => Verdict::Valid,
+            false => Verdict::Invalid(format!(
+                "{} '{}' found, expected {} to {}",
+                count, self.ch, min, max
+            )),
+        }
+    }
+
+    /// The character is at exactly one of the (1-indexed) positions `n1` and
+    /// `n2`.
+    pub fn by_position(&self) -> Verdict {
+        let at = |ix: usize| match ix.checked_sub(1) {
+            None => Err("position 0, but positions start at 1".to_string()),
+            Some(i) => self
+                .password
+                .chars()
+                .nth(i)
+                .map(|c| c == self.ch)
+                .ok_or_else(|| {
+                    format!(
+                        "position {} is past the end of the {}-character password",
+                        ix,
+                        self.password.chars().count()
+                    )
+                }),
+        };
+        let (ix1, ix2) = (self.n1, self.n2);
+        match (at(ix1), at(ix2)) {
+            (Err(e), _) | (_, Err(e)) => Verdict::Malformed(e),
+            (Ok(ch1), Ok(ch2)) if ch1 ^ ch2 => Verdict::Valid,
+            (Ok(true), Ok(true)) => {
+                Verdict::Invalid(format!("'{}' at both {} and {}", self.ch, ix1, ix2))
+            }
+            _ => Verdict::Invalid(format!("'{}' at neither {} nor {}", self.ch, ix1, ix2)),
+        }
+    }
+
+    pub fn valid_by_count(&self) -> bool {
+        self.by_count().is_valid()
+    }
+
+    pub fn valid_by_position(&self) -> bool {
+        self.by_position().is_valid()
+    }
+}
+
+impl std::fmt::Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{} {}: {}", self.n1, self.n2, self.ch, self.password)
+    }
+}
+
+/// Parses a line of the database, e.g. `1-3 a: abcde`.
+pub fn parse_entry(l: &str) -> std::result::Result<Entry, ParseError> {
+    let cap = RE_ENTRY
+        .captures(l)
+        .ok_or_else(|| ParseError::at(l, l, "a policy and password, like \"1-3 a: abcde\""))?;
+    Ok(Entry {
+        n1: parse::number(l, cap.get(1).unwrap().as_str())?,
+        n2: parse::number(l, cap.get(2).unwrap().as_str())?,
+        ch: cap[3].chars().next().unwrap(),
+        password: cap[4].to_string(),
+    })
+}
+
+/// Each line of the input with the entry parsed from it, or why it doesn't
+/// parse; unlike [`Day02::parse`], a bad line doesn't stop the rest.
+pub fn parse_each(input: &str) -> Vec<(String, std::result::Result<Entry, ParseError>)> {
+    let input = &parse::normalize(input);
+    input
+        .lines()
+        .map(|l| {
+            (
+                l.to_string(),
+                parse_entry(l).map_err(|e| e.within(input, l)),
+            )
+        })
+        .collect()
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Entry>;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Entry>> {
+        let input = &parse::normalize(input);
+        Ok(parse::lines(input, parse_entry)?)
+    }
+
+    // part 1 -- character count must be within min..=max
+    fn part1(entries: &Vec<Entry>) -> Result<usize> {
+        Ok(entries.iter().filter(|e| e.valid_by_count()).count())
+    }
+
+    // part 2 -- indices (1-indexed)
+    // character must appear at exactly one of the two indices
+    fn part2(entries: &Vec<Entry>) -> Result<usize> {
+        Ok(entries.iter().filter(|e| e.valid_by_position()).count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(line: &str) -> Entry {
+        Day02::parse(line).unwrap().remove(0)
+    }
+
+    #[test]
+    fn positions_outside_the_password_are_malformed() {
+        assert_eq!(
+            Verdict::Malformed("position 0, but positions start at 1".to_string()),
+            entry("0-2 a: ab").by_position()
+        );
+        assert_eq!(
+            Verdict::Malformed(
+                "position 9 is past the end of the 2-character password".to_string()
+            ),
+            entry("1-9 c: cc").by_position()
+        );
+        assert!(!entry("1-9 c: cc").valid_by_position());
+        assert_eq!(
+            Verdict::Invalid("'c' at both 1 and 2".to_string()),
+            entry("1-2 c: cc").by_position()
+        );
+    }
+
+    #[test]
+    fn empty_count_ranges_are_malformed() {
+        assert_eq!(
+            Verdict::Malformed("3-1 is an empty range".to_string()),
+            entry("3-1 d: dd").by_count()
+        );
+        assert_eq!(Verdict::Valid, entry("1-9 c: cc").by_count());
+    }
+}
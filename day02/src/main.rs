@@ -1,54 +1,62 @@
-use std::{
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-};
-
-use regex::Regex;
-
-fn main() -> Result<(), Box<dyn Error>> {
-    let file = File::open("input.txt")?;
-    let buffered = BufReader::new(file);
-
-    let rx = Regex::new("(\\d+)-(\\d+) ([a-z]{1}): ([a-z]+)")?;
-
-    let mut part1_valid_count = 0_usize;
-    let mut part2_valid_count = 0_usize;
-
-    for line in buffered.lines() {
-        let l = line?.to_string();
-        let cap = rx.captures(&l).ok_or("failed to parse line")?;
-
-        // part 1
-        let min: usize = cap[1].parse()?;
-        let max: usize = cap[2].parse()?;
-        let ch: char = cap[3].parse()?;
-        let password = &cap[4];
-
-        let count = password.chars().filter(|c| *c == ch).count();
-        if count >= min && count <= max {
-            part1_valid_count += 1;
+use std::path::Path;
+
+use common::Solution;
+use day02::{policy::Policies, Day02};
+
+fn main() -> common::Result<()> {
+    common::logging::init_from_env()?;
+
+    // --report checks every line against the built-in policies, --policies=FILE
+    // adds those in the file, and --csv writes the verdicts as CSV instead
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let file = args.iter().find_map(|a| a.strip_prefix("--policies="));
+    let flag = |name: &str| args.iter().any(|a| a == name);
+    if file.is_some() || flag("--report") || flag("--csv") {
+        let policies = match file {
+            Some(path) => Policies::load(Path::new(path))?,
+            None => Policies::builtin(),
+        };
+        // read line by line, so a line that doesn't parse is reported too
+        let input = common::input::source_from_args(2).read()?;
+        let report = policies.report(&input);
+        if flag("--csv") {
+            report.write_csv(&mut std::io::stdout().lock())?;
+            return Ok(());
         }
-
-        // part 2 -- indices (1-indexed)
-        // character must appear at exactly one of the two indices
-        let ix1: usize = cap[1].parse()?;
-        let ix2: usize = cap[2].parse()?;
-        let ch1 = password
-            .chars()
-            .nth(ix1 - 1)
-            .expect("character not present at index");
-        let ch2 = password
-            .chars()
-            .nth(ix2 - 1)
-            .expect("character not present at index");
-
-        if (ch1 == ch) ^ (ch2 == ch) {
-            part2_valid_count += 1;
+        for (number, line) in report.lines.iter().enumerate() {
+            let verdicts = &line.verdicts;
+            let passed: Vec<&str> = report
+                .names
+                .iter()
+                .zip(verdicts)
+                .filter(|(_, v)| v.is_valid())
+                .map(|(n, _)| *n)
+                .collect();
+            println!(
+                "{}: {}  passed: [{}]",
+                number + 1,
+                line.text,
+                passed.join(", ")
+            );
+            for (name, verdict) in report.names.iter().zip(verdicts) {
+                if let Some(reason) = verdict.reason() {
+                    println!("    {} {}: {}", name, verdict, reason);
+                }
+            }
         }
+        println!("{} lines", report.lines.len());
+        for (name, tally) in report.names.iter().zip(report.tally()) {
+            println!(
+                "{}: {} valid, {} invalid, {} malformed",
+                name, tally.valid, tally.invalid, tally.malformed
+            );
+        }
+        return Ok(());
     }
-    println!("Part1 -> Valid passwords: {}", part1_valid_count);
-    println!("Part2 -> Valid passwords: {}", part2_valid_count);
+
+    let entries = common::input::parse_from_args::<Day02>(2)?;
+    println!("Part1 -> Valid passwords: {}", Day02::part1(&entries)?);
+    println!("Part2 -> Valid passwords: {}", Day02::part2(&entries)?);
 
     Ok(())
 }
@@ -0,0 +1,518 @@
+//! Password policies beyond the puzzle's two, loaded from a TOML file, e.g.
+//!
+//! ```toml
+//! [[policy]]
+//! name = "vowel"
+//! rule = { regex = "[aeiou]" }
+//!
+//! [[policy]]
+//! name = "strict"
+//! rule = { all = [{ policy = "count" }, { policy = "vowel" }, { distinct = 4 }] }
+//! ```
+//!
+//! A composite refers to the built-in `count` and `position` policies, or to
+//! any defined above it, by name.
+
+use std::{collections::HashSet, io::Write, path::Path, rc::Rc};
+
+use common::Result;
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::{parse_each, Entry, Verdict};
+
+/// A rule a line's password either meets or doesn't.
+pub trait PasswordPolicy {
+    fn check(&self, entry: &Entry) -> Verdict;
+}
+
+/// Part 1's policy, [`Entry::by_count`].
+pub struct ByCount;
+
+impl PasswordPolicy for ByCount {
+    fn check(&self, entry: &Entry) -> Verdict {
+        entry.by_count()
+    }
+}
+
+/// Part 2's policy, [`Entry::by_position`].
+pub struct ByPosition;
+
+impl PasswordPolicy for ByPosition {
+    fn check(&self, entry: &Entry) -> Verdict {
+        entry.by_position()
+    }
+}
+
+fn valid_if(valid: bool, reason: impl FnOnce() -> String) -> Verdict {
+    match valid {
+        true => Verdict::Valid,
+        false => Verdict::Invalid(reason()),
+    }
+}
+
+/// The password matches the pattern somewhere.
+pub struct Matches(pub Regex);
+
+impl PasswordPolicy for Matches {
+    fn check(&self, entry: &Entry) -> Verdict {
+        valid_if(self.0.is_match(&entry.password), || {
+            format!("doesn't match /{}/", self.0)
+        })
+    }
+}
+
+/// The password doesn't contain the substring.
+pub struct Forbids(pub String);
+
+impl PasswordPolicy for Forbids {
+    fn check(&self, entry: &Entry) -> Verdict {
+        valid_if(!entry.password.contains(&self.0), || {
+            format!("contains \"{}\"", self.0)
+        })
+    }
+}
+
+/// The password has at least this many different characters.
+pub struct DistinctChars(pub usize);
+
+impl PasswordPolicy for DistinctChars {
+    fn check(&self, entry: &Entry) -> Verdict {
+        let distinct = entry.password.chars().collect::<HashSet<char>>().len();
+        valid_if(distinct >= self.0, || {
+            format!("{} distinct characters, expected {}", distinct, self.0)
+        })
+    }
+}
+
+/// Every one of the policies holds. Any that doesn't apply to the line makes
+/// the whole malformed; otherwise the reasons are those of the policies that
+/// fail.
+pub struct All(pub Vec<Rc<dyn PasswordPolicy>>);
+
+impl PasswordPolicy for All {
+    fn check(&self, entry: &Entry) -> Verdict {
+        let verdicts: Vec<Verdict> = self.0.iter().map(|p| p.check(entry)).collect();
+        let reasons = |malformed: bool| {
+            verdicts
+                .iter()
+                .filter(|v| matches!(v, Verdict::Malformed(_)) == malformed)
+                .filter_map(|v| v.reason())
+                .collect::<Vec<&str>>()
+                .join("; ")
+        };
+        if verdicts.iter().any(|v| matches!(v, Verdict::Malformed(_))) {
+            Verdict::Malformed(reasons(true))
+        } else {
+            valid_if(verdicts.iter().all(Verdict::is_valid), || reasons(false))
+        }
+    }
+}
+
+/// At least one of the policies holds. If none does, the line is malformed
+/// only when none of them applies to it.
+pub struct Any(pub Vec<Rc<dyn PasswordPolicy>>);
+
+impl PasswordPolicy for Any {
+    fn check(&self, entry: &Entry) -> Verdict {
+        let verdicts: Vec<Verdict> = self.0.iter().map(|p| p.check(entry)).collect();
+        if verdicts.iter().any(Verdict::is_valid) {
+            return Verdict::Valid;
+        }
+        let reasons = verdicts
+            .iter()
+            .filter_map(|v| v.reason())
+            .collect::<Vec<&str>>()
+            .join("; ");
+        match verdicts.iter().all(|v| matches!(v, Verdict::Malformed(_))) {
+            true if !verdicts.is_empty() => Verdict::Malformed(reasons),
+            _ => Verdict::Invalid(reasons),
+        }
+    }
+}
+
+/// A policy as written in the file.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum Rule {
+    /// A policy defined earlier, by name.
+    Policy(String),
+    Regex(String),
+    Forbid(String),
+    Distinct(usize),
+    All(Vec<Rule>),
+    Any(Vec<Rule>),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    #[serde(default)]
+    policy: Vec<Named>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Named {
+    name: String,
+    rule: Rule,
+}
+
+/// Named policies, in the order they were defined.
+pub struct Policies {
+    named: Vec<(String, Rc<dyn PasswordPolicy>)>,
+}
+
+/// Every line checked against every policy.
+pub struct Report<'a> {
+    /// The policies' names, in the order of each line's verdicts.
+    pub names: Vec<&'a str>,
+    pub lines: Vec<Line>,
+}
+
+/// A line of the input, and its verdict for each policy.
+pub struct Line {
+    pub text: String,
+    pub verdicts: Vec<Verdict>,
+}
+
+/// How many lines each verdict went to, for a policy.
+#[derive(Debug, Default, PartialEq)]
+pub struct Tally {
+    pub valid: usize,
+    pub invalid: usize,
+    pub malformed: usize,
+}
+
+impl Policies {
+    /// The puzzle's policies: `count` for part 1 and `position` for part 2.
+    pub fn builtin() -> Self {
+        Policies {
+            named: vec![
+                ("count".to_string(), Rc::new(ByCount)),
+                ("position".to_string(), Rc::new(ByPosition)),
+            ],
+        }
+    }
+
+    /// The built-in policies, then those in the file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+        Self::from_toml(&text).map_err(|e| format!("{}: {}", path.display(), e).into())
+    }
+
+    /// The built-in policies, then those in the TOML text.
+    pub fn from_toml(text: &str) -> Result<Self> {
+        let config: Config =
+            toml::from_str(text).map_err(|e| e.to_string().trim_end().to_string())?;
+        let mut policies = Self::builtin();
+        for Named { name, rule } in config.policy {
+            let policy = policies
+                .build(&rule)
+                .map_err(|e| format!("policy {}: {}", name, e))?;
+            policies.add(name, policy)?;
+        }
+        Ok(policies)
+    }
+
+    /// Adds a policy after the others; names must be unique.
+    pub fn add(&mut self, name: String, policy: Rc<dyn PasswordPolicy>) -> Result<()> {
+        if self.get(&name).is_some() {
+            return Err(format!("policy {} is defined twice", name).into());
+        }
+        self.named.push((name, policy));
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<Rc<dyn PasswordPolicy>> {
+        self.named
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, p)| p.clone())
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.named.iter().map(|(n, _)| n.as_str())
+    }
+
+    fn build(&self, rule: &Rule) -> Result<Rc<dyn PasswordPolicy>> {
+        let all = |rules: &[Rule]| rules.iter().map(|r| self.build(r)).collect::<Result<_>>();
+        Ok(match rule {
+            Rule::Policy(name) => self
+                .get(name)
+                .ok_or_else(|| format!("no policy {} defined before it", name))?,
+            Rule::Regex(pattern) => Rc::new(Matches(
+                Regex::new(pattern).map_err(|e| format!("bad regex: {}", e))?,
+            )),
+            Rule::Forbid(text) => Rc::new(Forbids(text.clone())),
+            Rule::Distinct(n) => Rc::new(DistinctChars(*n)),
+            Rule::All(rules) => Rc::new(All(all(rules)?)),
+            Rule::Any(rules) => Rc::new(Any(all(rules)?)),
+        })
+    }
+
+    /// Checks a line against every policy, in order.
+    pub fn check(&self, entry: &Entry) -> Vec<Verdict> {
+        self.named.iter().map(|(_, p)| p.check(entry)).collect()
+    }
+
+    /// Checks every line of the input; a line that doesn't parse is
+    /// malformed for every policy, with the parse error as the reason.
+    pub fn report(&self, input: &str) -> Report<'_> {
+        let lines = parse_each(input).into_iter().map(|(text, entry)| {
+            let verdicts = match entry {
+                Ok(entry) => self.check(&entry),
+                Err(e) => self
+                    .named
+                    .iter()
+                    .map(|_| Verdict::Malformed(e.to_string()))
+                    .collect(),
+            };
+            Line { text, verdicts }
+        });
+        Report {
+            names: self.names().collect(),
+            lines: lines.collect(),
+        }
+    }
+}
+
+impl Report<'_> {
+    /// The verdicts for each policy, in order.
+    pub fn tally(&self) -> Vec<Tally> {
+        let mut tally: Vec<Tally> = self.names.iter().map(|_| Tally::default()).collect();
+        for line in self.lines.iter() {
+            for (t, v) in tally.iter_mut().zip(line.verdicts.iter()) {
+                match v {
+                    Verdict::Valid => t.valid += 1,
+                    Verdict::Invalid(_) => t.invalid += 1,
+                    Verdict::Malformed(_) => t.malformed += 1,
+                }
+            }
+        }
+        tally
+    }
+
+    /// Writes a row per line and policy: the (1-indexed) line, the line
+    /// itself, the policy, its verdict and the reason for it.
+    pub fn write_csv(&self, out: &mut impl Write) -> std::io::Result<()> {
+        writeln!(out, "line,entry,policy,verdict,reason")?;
+        for (number, line) in self.lines.iter().enumerate() {
+            for (name, verdict) in self.names.iter().zip(line.verdicts.iter()) {
+                writeln!(
+                    out,
+                    "{},{},{},{},{}",
+                    number + 1,
+                    csv_field(&line.text),
+                    csv_field(name),
+                    verdict,
+                    csv_field(verdict.reason().unwrap_or(""))
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// quoted if need be, with quotes doubled
+fn csv_field(text: &str) -> String {
+    match text.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", text.replace('"', "\"\"")),
+        false => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day02;
+    use common::Solution;
+
+    const EXAMPLE: &str = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n";
+
+    #[test]
+    fn builtin_policies_are_the_parts() {
+        let entries = Day02::parse(EXAMPLE).unwrap();
+        let policies = Policies::builtin();
+        let report = policies.report(EXAMPLE);
+        let tally = report.tally();
+        assert_eq!(Day02::part1(&entries).unwrap(), tally[0].valid);
+        assert_eq!(Day02::part2(&entries).unwrap(), tally[1].valid);
+        assert_eq!(
+            vec![
+                Verdict::Invalid("0 'b' found, expected 1 to 3".to_string()),
+                Verdict::Invalid("'b' at neither 1 nor 3".to_string()),
+            ],
+            report.lines[1].verdicts
+        );
+    }
+
+    #[test]
+    fn policies_from_toml() {
+        let policies = Policies::from_toml(
+            r#"
+            [[policy]]
+            name = "vowel"
+            rule = { regex = "[aeiou]" }
+
+            [[policy]]
+            name = "no-cc"
+            rule = { forbid = "cc" }
+
+            [[policy]]
+            name = "varied"
+            rule = { distinct = 5 }
+
+            [[policy]]
+            name = "strict"
+            rule = { all = [{ policy = "count" }, { policy = "vowel" }, { distinct = 4 }] }
+
+            [[policy]]
+            name = "either"
+            rule = { any = [{ policy = "position" }, { forbid = "c" }] }
+            "#,
+        )
+        .unwrap();
+        let names: Vec<&str> = policies.names().collect();
+        assert_eq!(
+            vec!["count", "position", "vowel", "no-cc", "varied", "strict", "either"],
+            names
+        );
+
+        let entries = Day02::parse(EXAMPLE).unwrap();
+        let passed: Vec<Vec<&str>> = entries
+            .iter()
+            .map(|e| {
+                let verdicts = policies.check(e);
+                let passed = verdicts.iter().map(Verdict::is_valid);
+                policies
+                    .names()
+                    .zip(passed)
+                    .filter(|(_, p)| *p)
+                    .map(|(n, _)| n)
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                vec!["count", "position", "vowel", "no-cc", "varied", "strict", "either"],
+                vec!["vowel", "no-cc", "varied"],
+                vec!["count"],
+            ],
+            passed
+        );
+    }
+
+    #[test]
+    fn bad_policies_are_reported() {
+        let error = |text: &str| Policies::from_toml(text).err().unwrap().to_string();
+        assert_eq!(
+            "policy a: no policy b defined before it",
+            error("[[policy]]\nname = \"a\"\nrule = { policy = \"b\" }")
+        );
+        assert!(error("[[policy]]\nname = \"a\"\nrule = { regex = \"(\" }")
+            .starts_with("policy a: bad regex"));
+        assert_eq!(
+            "policy count is defined twice",
+            error("[[policy]]\nname = \"count\"\nrule = { distinct = 1 }")
+        );
+        assert!(error("[[policy]]\nname = \"a\"\nrule = { longest = 3 }").contains("longest"));
+    }
+
+    #[test]
+    fn malformed_lines_are_reported_as_csv() {
+        let policies = Policies::from_toml(
+            r#"
+            [[policy]]
+            name = "both"
+            rule = { all = [{ policy = "count" }, { regex = "x|y,z" }] }
+
+            [[policy]]
+            name = "either"
+            rule = { any = [{ policy = "position" }, { forbid = "d" }] }
+            "#,
+        )
+        .unwrap();
+        let report = policies.report("0-2 a: ab\n1-9 c: cc\n3-1 d: dd\n");
+        assert_eq!(
+            vec![
+                Tally {
+                    valid: 2,
+                    invalid: 0,
+                    malformed: 1
+                },
+                Tally {
+                    valid: 0,
+                    invalid: 0,
+                    malformed: 3
+                },
+                Tally {
+                    valid: 0,
+                    invalid: 2,
+                    malformed: 1
+                },
+                Tally {
+                    valid: 2,
+                    invalid: 1,
+                    malformed: 0
+                },
+            ],
+            report.tally()
+        );
+
+        let mut csv = Vec::new();
+        report.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(13, rows.len());
+        assert_eq!("line,entry,policy,verdict,reason", rows[0]);
+        assert_eq!(
+            "1,0-2 a: ab,position,malformed,\"position 0, but positions start at 1\"",
+            rows[2]
+        );
+        assert_eq!(
+            "2,1-9 c: cc,both,invalid,\"doesn't match /x|y,z/\"",
+            rows[7]
+        );
+        assert_eq!(
+            "3,3-1 d: dd,either,invalid,\"position 3 is past the end of the 2-character password; contains \"\"d\"\"\"",
+            rows[12]
+        );
+    }
+
+    #[test]
+    fn unparseable_lines_are_malformed() {
+        let policies = Policies::builtin();
+        let report = policies.report("1-3 a: abcde\r\n1-3 b:\r\n1-3 b: cdefg\r\n");
+        let reason = "line 2, column 1: expected a policy and password, like \"1-3 a: abcde\", found \"1-3 b:\"";
+        assert_eq!("1-3 b:", report.lines[1].text);
+        assert_eq!(
+            vec![Verdict::Malformed(reason.to_string()); 2],
+            report.lines[1].verdicts
+        );
+        assert!(report.lines[2]
+            .verdicts
+            .iter()
+            .all(|v| matches!(v, Verdict::Invalid(_))));
+        assert_eq!(
+            Tally {
+                valid: 1,
+                invalid: 1,
+                malformed: 1
+            },
+            report.tally()[0]
+        );
+
+        let mut csv = Vec::new();
+        report.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(
+            format!(
+                "2,1-3 b:,count,malformed,\"{}\"",
+                reason.replace('"', "\"\"")
+            ),
+            csv.lines().nth(3).unwrap()
+        );
+    }
+}
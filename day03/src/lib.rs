@@ -0,0 +1,294 @@
+use common::{Result, Solution};
+use grid::{Grid, Pos, Wrap};
+use serde::{Deserialize, Serialize};
+
+/// The slope of part 1, as (right, down).
+pub const SLOPE: (i64, i64) = (3, 1);
+
+/// The slopes of part 2, as (right, down).
+pub const SLOPES: [(i64, i64); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+/// The puzzle constants, as in the puzzle unless changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    /// The slope of part 1, as (right, down).
+    pub slope: (i64, i64),
+    /// The slopes whose tree counts part 2 multiplies.
+    pub slopes: Vec<(i64, i64)>,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            slope: SLOPE,
+            slopes: SLOPES.to_vec(),
+        }
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Grid<bool>;
+    type Params = Params;
+    type Part1 = i32;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Grid<bool>> {
+        Ok(Grid::parse(&common::parse::normalize(input))?)
+    }
+
+    fn part1(map: &Grid<bool>) -> Result<i32> {
+        Self::part1_with(map, &Params::default())
+    }
+
+    fn part2(map: &Grid<bool>) -> Result<i64> {
+        Self::part2_with(map, &Params::default())
+    }
+
+    // part 1
+    fn part1_with(map: &Grid<bool>, params: &Params) -> Result<i32> {
+        let (dx, dy) = downhill(params.slope)?;
+        Ok(count_encountered_trees(map, dx, dy))
+    }
+
+    // part 2
+    fn part2_with(map: &Grid<bool>, params: &Params) -> Result<i64> {
+        for &slope in params.slopes.iter() {
+            downhill(slope)?;
+        }
+        let counts = params
+            .slopes
+            .iter()
+            .map(|(dx, dy)| count_encountered_trees(map, *dx, *dy));
+        let product: i64 = counts.map(|v| v as i64).product(); // overflows with i32
+        Ok(product)
+    }
+}
+
+// a slope that never gets past the bottom of the map would go on forever
+fn downhill((dx, dy): (i64, i64)) -> Result<(i64, i64)> {
+    match dy {
+        1.. => Ok((dx, dy)),
+        _ => Err(format!("slope ({}, {}) doesn't go down", dx, dy).into()),
+    }
+}
+
+// the map repeats to the right, so columns wrap
+pub fn encountered_tree(map: &Grid<bool>, pos: Pos) -> bool {
+    map.get_wrapped(pos, Wrap::Cols) == Some(&true)
+}
+
+/// Trees hit going `dx` right and `dy` down from the top left until past
+/// the bottom of the map.
+pub fn count_encountered_trees(map: &Grid<bool>, dx: i64, dy: i64) -> i32 {
+    let step = Pos::new(dy, dx);
+    let mut pos = Pos::default();
+    let mut trees = 0;
+    loop {
+        pos += step;
+
+        if pos.row >= map.rows() as i64 {
+            break;
+        }
+
+        if encountered_tree(map, pos) {
+            trees += 1;
+        }
+    }
+    trees
+}
+
+/// The most landings [`count_trees_on_line`] makes on the way down; a
+/// nearly level slope would otherwise take practically forever.
+pub const MAX_LANDINGS: f64 = 1e8;
+
+/// Trees hit landing every `right` across and `down` down from the top left
+/// until past the bottom of the map, as in the puzzle but with slopes that
+/// needn't be whole numbers: every landing is on the exact line, in whichever
+/// square it falls in. Landing in the same square again straight away hits
+/// its tree only once. A whole slope hits the same trees as by
+/// [`count_encountered_trees`].
+pub fn count_trees_on_line(map: &Grid<bool>, right: f64, down: f64) -> Result<i32> {
+    if !(down > 0.0 && down.is_finite() && right.is_finite()) {
+        return Err(format!("slope ({}, {}) doesn't go down", right, down).into());
+    }
+    let landings = (map.rows() as f64 / down).ceil();
+    if landings > MAX_LANDINGS {
+        return Err(format!(
+            "slope ({}, {}) would land {} times on the way down, more than {}",
+            right, down, landings, MAX_LANDINGS
+        )
+        .into());
+    }
+
+    let mut last = Pos::default();
+    let mut trees = 0;
+    for t in 1.. {
+        let t = t as f64;
+        let pos = Pos::new(square_index(t * down), square_index(t * right));
+
+        if pos.row >= map.rows() as i64 {
+            break;
+        }
+
+        if pos != last && encountered_tree(map, pos) {
+            trees += 1;
+        }
+        last = pos;
+    }
+    Ok(trees)
+}
+
+// the index of the square a coordinate falls in; one a rounding error short
+// of a boundary, like 0.7 * 90, is taken to be on it. A product is off by
+// at most half a unit in its last place, so the slack is a few of those at
+// the coordinate's own size, not a fixed amount that large coordinates
+// can't get within
+fn square_index(x: f64) -> i64 {
+    let nearest = x.round();
+    if (x - nearest).abs() <= 4.0 * f64::EPSILON * x.abs() {
+        nearest as i64
+    } else {
+        x.floor() as i64
+    }
+}
+
+/// The values from `from` to `to` going up by `by`, for [`explore`]; each
+/// is rounded to nine decimal places so that, say, tenths stay tenths.
+pub fn steps(from: f64, to: f64, by: f64) -> Result<Vec<f64>> {
+    if !(by > 0.0 && by.is_finite() && from.is_finite() && to.is_finite()) {
+        return Err(format!("can't go from {} to {} by {}", from, to, by).into());
+    }
+    let count = ((to - from) / by + 1e-9).floor() + 1.0;
+    if count > 1e6 {
+        return Err(format!("{} to {} by {} is {} values", from, to, by, count).into());
+    }
+    let values = (0..count.max(0.0) as i64).map(|i| from + i as f64 * by);
+    Ok(values.map(|v| (v * 1e9).round() / 1e9).collect())
+}
+
+/// The trees hit on each slope, as (right, down), in a range, and how many
+/// slopes in it were skipped for not going down.
+pub struct Exploration {
+    pub trees: Vec<((f64, f64), i32)>,
+    pub skipped: usize,
+}
+
+/// Trees hit, by [`count_trees_on_line`], on every slope going right by each
+/// of `right` and down by each of `down`, skipping those that don't go down.
+pub fn explore(map: &Grid<bool>, right: &[f64], down: &[f64]) -> Result<Exploration> {
+    let mut trees = Vec::new();
+    let mut skipped = 0;
+    for &dy in down {
+        for &dx in right {
+            if dy <= 0.0 {
+                skipped += 1;
+                continue;
+            }
+            trees.push(((dx, dy), count_trees_on_line(map, dx, dy)?));
+        }
+    }
+    Ok(Exploration { trees, skipped })
+}
+
+impl Exploration {
+    /// The fewest trees any slope hits, and the slopes hitting that many.
+    pub fn fewest(&self) -> Option<(i32, Vec<(f64, f64)>)> {
+        self.with(self.trees.iter().map(|(_, t)| *t).min()?)
+    }
+
+    /// The most trees any slope hits, and the slopes hitting that many.
+    pub fn most(&self) -> Option<(i32, Vec<(f64, f64)>)> {
+        self.with(self.trees.iter().map(|(_, t)| *t).max()?)
+    }
+
+    fn with(&self, count: i32) -> Option<(i32, Vec<(f64, f64)>)> {
+        let slopes = self.trees.iter().filter(|(_, t)| *t == count);
+        Some((count, slopes.map(|(s, _)| *s).collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Grid<bool> {
+        Day03::parse(include_str!("../example_input.txt")).unwrap()
+    }
+
+    #[test]
+    fn exploration_finds_fewest_and_most() {
+        let map = example();
+        let right = steps(0.0, 7.0, 1.0).unwrap();
+        let exploration = explore(&map, &right, &steps(1.0, 2.0, 1.0).unwrap()).unwrap();
+        assert_eq!(16, exploration.trees.len());
+        for &((dx, dy), trees) in exploration.trees.iter() {
+            assert_eq!(count_encountered_trees(&map, dx as i64, dy as i64), trees);
+        }
+        assert_eq!(Some((0, vec![(5.0, 2.0)])), exploration.fewest());
+        assert_eq!(Some((7, vec![(3.0, 1.0)])), exploration.most());
+
+        // slopes that don't go down are left out rather than failing the rest
+        let exploration = explore(&map, &[0.0, 1.0], &steps(-0.5, 1.0, 0.5).unwrap()).unwrap();
+        assert_eq!(4, exploration.skipped);
+        let slopes: Vec<_> = exploration.trees.iter().map(|(s, _)| *s).collect();
+        assert_eq!(vec![(0.0, 0.5), (1.0, 0.5), (0.0, 1.0), (1.0, 1.0)], slopes);
+
+        assert_eq!(None, explore(&map, &[], &[1.0]).unwrap().fewest());
+    }
+
+    #[test]
+    fn landings_a_rounding_error_short_are_on_the_boundary() {
+        assert_eq!(63, square_index(90.0 * 0.7));
+        assert_eq!(230_000_000, square_index(100_000_000.0 * 2.3));
+        assert_eq!(
+            70_000_000_000_000,
+            square_index(100_000_000_000_000.0 * 0.7)
+        );
+        assert_eq!(2, square_index(2.5));
+        assert_eq!(-3, square_index(-2.5));
+    }
+
+    #[test]
+    fn stepped_ranges() {
+        assert_eq!(vec![0.0, 0.5, 1.0], steps(0.0, 1.0, 0.5).unwrap());
+        assert_eq!(vec![0.1, 0.2, 0.3], steps(0.1, 0.3, 0.1).unwrap());
+        assert_eq!(vec![2.0], steps(2.0, 2.0, 1.0).unwrap());
+        assert!(steps(1.0, 0.0, 1.0).unwrap().is_empty());
+        assert!(steps(0.0, 1.0, 0.0).is_err());
+        assert!(steps(0.0, 1.0, 1e-12).is_err());
+    }
+
+    #[test]
+    fn lines_through_the_map() {
+        let map = example();
+        // whole slopes land where the puzzle does
+        for dy in 1..=3 {
+            for dx in -4..=8 {
+                assert_eq!(
+                    count_encountered_trees(&map, dx, dy),
+                    count_trees_on_line(&map, dx as f64, dy as f64).unwrap(),
+                    "slope ({}, {})",
+                    dx,
+                    dy
+                );
+            }
+        }
+        // half of the diagonal lands twice in each of its squares
+        assert_eq!(
+            count_encountered_trees(&map, 1, 1),
+            count_trees_on_line(&map, 0.5, 0.5).unwrap()
+        );
+
+        // between squares, a landing is in the square it falls in
+        let map = Day03::parse("....\n.#..\n...#\n").unwrap();
+        assert_eq!(0, count_trees_on_line(&map, 0.5, 1.0).unwrap());
+        assert_eq!(2, count_trees_on_line(&map, 1.5, 1.0).unwrap());
+        assert_eq!(1, count_trees_on_line(&map, -0.5, 1.0).unwrap());
+
+        assert!(count_trees_on_line(&map, 1.0, 0.0).is_err());
+        assert!(count_trees_on_line(&map, 1.0, 1e-12).is_err());
+    }
+}
@@ -1,67 +1,67 @@
-use core::panic;
-use std::{
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use common::Solution;
+use day03::Day03;
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let file = File::open("input.txt")?;
-    let buffered = BufReader::new(file);
+fn main() -> common::Result<()> {
+    common::logging::init_from_env()?;
+    let map = common::input::parse_from_args::<Day03>(3)?;
+    let params = common::params::from_args::<Day03>(3)?;
 
-    let map: Vec<Vec<bool>> = buffered.lines().map(|l| parse_line(&l.unwrap())).collect();
-    //println!("{:?}", map);
-
-    // part 1
-    {
-        let trees = count_encountered_trees(&map, 3, 1);
-        println!("Part1 -> trees = {}", trees);
+    // --explore=RIGHT,DOWN tries every slope in the ranges, like
+    // --explore=0..=10,1..=3 or, in quarters, --explore=0..=2:0.25,1, and
+    // --line=RIGHT,DOWN follows any slope exactly
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(ranges) = args.iter().find_map(|a| a.strip_prefix("--explore=")) {
+        let (right, down) = pair(ranges)?;
+        let exploration = day03::explore(&map, &range(right)?, &range(down)?)?;
+        let (fewest, most) = exploration
+            .fewest()
+            .zip(exploration.most())
+            .ok_or("no slopes")?;
+        println!(
+            "{} slopes, skipping {} that don't go down",
+            exploration.trees.len(),
+            exploration.skipped
+        );
+        println!("fewest -> {} trees on {:?}", fewest.0, fewest.1);
+        println!("most -> {} trees on {:?}", most.0, most.1);
+        return Ok(());
     }
-
-    // part 2
-    {
-        let paths = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
-        let counts = paths
-            .iter()
-            .map(|(dx, dy)| count_encountered_trees(&map, *dx, *dy));
-        let product:i64 = counts.map(|v| v as i64).product(); // overflows with i32
-        println!("Part2 -> product = {}", product);
+    if let Some(slope) = args.iter().find_map(|a| a.strip_prefix("--line=")) {
+        let (right, down) = pair(slope)?;
+        let number = |n: &str| {
+            n.parse::<f64>()
+                .map_err(|e| format!("bad slope {}: {}", n, e))
+        };
+        let trees = day03::count_trees_on_line(&map, number(right)?, number(down)?)?;
+        println!("line -> trees = {}", trees);
+        return Ok(());
     }
 
-    Ok(())
-}
+    println!("Part1 -> trees = {}", Day03::part1_with(&map, &params)?);
+    println!("Part2 -> product = {}", Day03::part2_with(&map, &params)?);
 
-fn parse_line(line: &str) -> Vec<bool> {
-    line.chars()
-        .map(|ch| match ch {
-            '.' => false,
-            '#' => true,
-            _ => panic!("unexpected char"),
-        })
-        .collect()
+    Ok(())
 }
 
-fn encountered_tree(map: &Vec<Vec<bool>>, x: usize, y: usize) -> bool {
-    let row = &map[y];
-    let x_mod = x % row.len();
-    row[x_mod]
+fn pair(text: &str) -> common::Result<(&str, &str)> {
+    Ok(text
+        .split_once(',')
+        .ok_or_else(|| format!("expected RIGHT,DOWN, found {}", text))?)
 }
 
-fn count_encountered_trees(map: &Vec<Vec<bool>>, dx: usize, dy: usize) -> i32 {
-    let mut x = 0_usize;
-    let mut y = 0_usize;
-    let mut trees = 0;
-    loop {
-        x += dx;
-        y += dy;
-
-        if y >= map.len() {
-            break;
-        }
-
-        if encountered_tree(&map, x, y) {
-            trees += 1;
-        }
+// `a..=b`, going up by 1 unless followed by `:step`, or a single number
+fn range(text: &str) -> common::Result<Vec<f64>> {
+    let number = |n: &str| {
+        n.trim()
+            .parse::<f64>()
+            .map_err(|e| format!("bad range {}: {}", text, e))
+    };
+    let (span, by) = match text.split_once(':') {
+        Some((span, by)) => (span, number(by)?),
+        None => (text, 1.0),
+    };
+    match span.split_once("..=") {
+        Some((from, to)) => day03::steps(number(from)?, number(to)?, by),
+        None => day03::steps(number(span)?, number(span)?, by),
     }
-    trees
 }
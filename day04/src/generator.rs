@@ -0,0 +1,175 @@
+// Passports with a mix of missing fields and out-of-range values.
+
+use generate::{Generate, Generated, Rng};
+
+use crate::Day04;
+
+const EYE_COLOURS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+fn year(rng: &mut Rng, min: i32, max: i32, valid: bool) -> String {
+    let year = match valid {
+        true => rng.i32(min..=max),
+        false if rng.bool() => rng.i32(min - 20..min),
+        false => rng.i32(max + 1..=max + 20),
+    };
+    year.to_string()
+}
+
+fn height(rng: &mut Rng, valid: bool) -> String {
+    match (valid, rng.bool()) {
+        (true, true) => format!("{}cm", rng.i32(150..=193)),
+        (true, false) => format!("{}in", rng.i32(59..=76)),
+        (false, true) => format!("{}cm", rng.i32(100..150)),
+        // no unit at all
+        (false, false) => rng.i32(59..=193).to_string(),
+    }
+}
+
+fn hair_colour(rng: &mut Rng, valid: bool) -> String {
+    let hex: String = (0..6)
+        .map(|_| std::char::from_digit(rng.u32(..16), 16).unwrap())
+        .collect();
+    match (valid, rng.bool()) {
+        (true, _) => format!("#{}", hex),
+        (false, true) => hex,
+        (false, false) => format!("#{}z", &hex[1..]),
+    }
+}
+
+fn eye_colour(rng: &mut Rng, valid: bool) -> String {
+    match valid {
+        true => EYE_COLOURS[rng.usize(..EYE_COLOURS.len())].to_string(),
+        false => "xyz".to_string(),
+    }
+}
+
+fn passport_id(rng: &mut Rng, valid: bool) -> String {
+    let digits = match (valid, rng.bool()) {
+        (true, _) => 9,
+        (false, true) => 8,
+        (false, false) => 10,
+    };
+    (0..digits).map(|_| rng.digit(10)).collect()
+}
+
+fn field_value(rng: &mut Rng, name: &str, valid: bool) -> String {
+    match name {
+        "byr" => year(rng, 1920, 2002, valid),
+        "iyr" => year(rng, 2010, 2020, valid),
+        "eyr" => year(rng, 2020, 2030, valid),
+        "hgt" => height(rng, valid),
+        "hcl" => hair_colour(rng, valid),
+        "ecl" => eye_colour(rng, valid),
+        "pid" => passport_id(rng, valid),
+        _ => rng.u32(1..1000).to_string(),
+    }
+}
+
+impl Generate for Day04 {
+    const SIZE_OF: &'static str = "passports";
+    const DEFAULT_SIZE: usize = 300;
+
+    // a third of the passports miss a required field, and a third of the
+    // rest have a field that doesn't validate
+    fn valid(rng: &mut Rng, size: usize) -> Generated {
+        let mut passports = Vec::new();
+        let mut complete = 0;
+        let mut valid = 0;
+        for _ in 0..size {
+            let mut names: Vec<&str> = crate::REQUIRED_FIELDS.to_vec();
+            if rng.bool() {
+                names.push("cid");
+            }
+            let missing = rng.usize(..3) == 0;
+            if missing {
+                names.remove(rng.usize(..crate::REQUIRED_FIELDS.len()));
+            }
+            let bad = match rng.usize(..3) {
+                0 => Some(names[rng.usize(..names.len())]).filter(|&n| n != "cid"),
+                _ => None,
+            };
+            rng.shuffle(&mut names);
+
+            if !missing {
+                complete += 1;
+                if bad.is_none() {
+                    valid += 1;
+                }
+            }
+
+            // fields are split over a few lines
+            let mut text = String::new();
+            for (i, name) in names.iter().enumerate() {
+                if i > 0 {
+                    text.push(if rng.usize(..4) == 0 { '\n' } else { ' ' });
+                }
+                let value = field_value(rng, name, bad != Some(name));
+                text.push_str(&format!("{}:{}", name, value));
+            }
+            passports.push(text);
+        }
+
+        Generated::new(passports.join("\n\n") + "\n")
+            .with_part1(complete)
+            .with_part2(valid)
+    }
+
+    fn corrupt(rng: &mut Rng, line: &str) -> String {
+        let tokens: Vec<&str> = line.split(' ').collect();
+        let spoil = rng.usize(..tokens.len());
+        let spoiled: Vec<String> = tokens
+            .iter()
+            .enumerate()
+            .map(|(i, &token)| match i == spoil {
+                // no separator, or a name that isn't lower case
+                true if rng.bool() => token.replacen(':', "", 1),
+                true => token.to_uppercase(),
+                false => token.to_string(),
+            })
+            .collect();
+        spoiled.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+    use generate::{generate, Validity};
+
+    #[test]
+    fn generated_passports_have_the_expected_answers() {
+        for seed in 0..10 {
+            let generated = generate::<Day04>(seed, 50, Validity::Valid).unwrap();
+            let passports = Day04::parse(&generated.input).unwrap();
+            assert_eq!(passports.len(), 50);
+            assert_eq!(
+                Some(Day04::part1(&passports).unwrap().to_string()),
+                generated.part1
+            );
+            assert_eq!(
+                Some(Day04::part2(&passports).unwrap().to_string()),
+                generated.part2
+            );
+        }
+    }
+
+    #[test]
+    fn corrupted_passports_fail_to_parse() {
+        for seed in 0..20 {
+            let generated = generate::<Day04>(seed, 20, Validity::Invalid).unwrap();
+            assert!(
+                Day04::parse(&generated.input).is_err(),
+                "{}",
+                generated.input
+            );
+        }
+    }
+
+    #[test]
+    fn seeds_are_reproducible() {
+        let input = |seed| generate::<Day04>(seed, 20, Validity::Valid).unwrap().input;
+        assert_eq!(input(1), input(1));
+        assert_ne!(input(1), input(2));
+    }
+}
@@ -0,0 +1,158 @@
+use common::{ParseError, Result, Solution};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+mod generator;
+
+/// A `name:value` pair, e.g. `byr:1937`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Field {
+    pub name: String,
+    pub value: String,
+}
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Passport {
+    pub fields: Vec<Field>,
+}
+
+pub const REQUIRED_FIELDS: [&str; 7] = [
+    "byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid",
+    //"cid",  // not required
+];
+
+/// Passports are separated by blank lines; any run of them counts as one.
+pub fn parse_input(contents: &str) -> std::result::Result<Vec<Passport>, ParseError> {
+    let contents = &common::parse::normalize(contents);
+    let passports_data: Vec<_> = contents
+        .split("\n\n")
+        .filter(|p| !p.trim().is_empty())
+        .collect();
+
+    let mut passports = Vec::new();
+    for p in passports_data {
+        let mut fields = Vec::new();
+        for token in p.split_whitespace() {
+            // e.g. byr:1937
+            let (name, value) = token
+                .split_once(':')
+                .filter(|(name, value)| {
+                    !name.is_empty()
+                        && name.chars().all(|c| c.is_ascii_lowercase())
+                        && !value.is_empty()
+                })
+                .ok_or_else(|| ParseError::at(contents, token, "a field like \"byr:1937\""))?;
+            fields.push(Field {
+                name: name.to_string(),
+                value: value.to_string(),
+            });
+        }
+        passports.push(Passport { fields });
+    }
+    Ok(passports)
+}
+
+pub fn has_required_fields(p: &Passport) -> bool {
+    REQUIRED_FIELDS
+        .iter()
+        .all(|&expected| p.fields.iter().any(|f| f.name == expected))
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<Passport>;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Passport>> {
+        Ok(parse_input(input)?)
+    }
+
+    // part 1
+    fn part1(passports: &Vec<Passport>) -> Result<usize> {
+        Ok(passports.iter().filter(|p| has_required_fields(p)).count())
+    }
+
+    // part 2 -- validation
+    fn part2(passports: &Vec<Passport>) -> Result<usize> {
+        let mut valid_count = 0;
+        for p in passports {
+            // check we have all the expected fields
+            let has_all_fields = has_required_fields(p);
+
+            // check all the fields are valid
+            let fields_are_valid = p.fields.iter().all(field_validate);
+
+            if has_all_fields && fields_are_valid {
+                valid_count += 1;
+            }
+        }
+        Ok(valid_count)
+    }
+}
+
+/// Whether a field's value follows its rules; unknown fields are invalid.
+pub fn field_validate(field: &Field) -> bool {
+    match field.name.as_str() {
+        "byr" => year_validate(&field.value, 1920, 2002),
+        "iyr" => year_validate(&field.value, 2010, 2020),
+        "eyr" => year_validate(&field.value, 2020, 2030),
+        "hgt" => height_validate(&field.value),
+        "hcl" => hair_colour_validate(&field.value),
+        "ecl" => eye_colour_validate(&field.value),
+        "pid" => passport_number_valid(&field.value),
+        "cid" => true, // always accepted
+        _ => {
+            log::warn!("invalid field: {:?}", field);
+            false
+        }
+    }
+}
+
+fn year_validate(value: &str, min: i32, max: i32) -> bool {
+    if let Ok(num) = value.parse::<i32>() {
+        (min..=max).contains(&num)
+    } else {
+        false
+    }
+}
+
+fn height_validate(value: &str) -> bool {
+    let rx = Regex::new("(\\d+)(cm|in)").expect("regex");
+    if let Some(cap) = rx.captures(value) {
+        if let Ok(num) = cap[1].parse::<i32>() {
+            let unit = &cap[2];
+            match unit {
+                "cm" => (150..=193).contains(&num),
+                "in" => (59..=76).contains(&num),
+                _ => false,
+            }
+        } else {
+            false
+        }
+    } else {
+        false
+    }
+}
+
+fn hair_colour_validate(value: &str) -> bool {
+    if value.len() != 7 {
+        return false;
+    }
+    if !value.starts_with('#') {
+        return false;
+    }
+    value
+        .chars()
+        .skip(1)
+        .all(|c| matches!(c, '0'..='9' | 'a'..='f'))
+}
+
+fn eye_colour_validate(value: &str) -> bool {
+    matches!(value, "amb" | "blu" | "brn" | "gry" | "grn" | "hzl" | "oth")
+}
+
+fn passport_number_valid(value: &str) -> bool {
+    value.chars().count() == 9 && value.chars().all(|c| c.is_ascii_digit())
+}
@@ -0,0 +1,126 @@
+use common::{parse, ParseError, Result, Solution};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Seat {
+    pub row: i32,
+    pub col: i32,
+    pub id: i32,
+}
+
+pub fn parse_input(input: &str) -> std::result::Result<Vec<Seat>, ParseError> {
+    let input = &parse::normalize(input);
+    let seats: Vec<Seat> = parse::lines(input, parse_seat)?;
+    for s in &seats {
+        log::debug!("{:?}", s);
+    }
+    Ok(seats)
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Vec<Seat>;
+    type Params = ();
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<Seat>> {
+        Ok(parse_input(input)?)
+    }
+
+    // a seat's id follows from its row and column
+    fn validate(seats: &Vec<Seat>) -> Result<()> {
+        for s in seats {
+            if !(0..128).contains(&s.row)
+                || !(0..8).contains(&s.col)
+                || s.id != seat_id(s.row, s.col)
+            {
+                return Err(format!("no such seat: {:?}", s).into());
+            }
+        }
+        Ok(())
+    }
+
+    // part 1 -- max id
+    fn part1(seats: &Vec<Seat>) -> Result<i32> {
+        let max = seats.iter().map(|s| s.id).max().ok_or("no max")?;
+        Ok(max)
+    }
+
+    // part 2 -- find missing seat (not first or last row)
+    fn part2(seats: &Vec<Seat>) -> Result<i32> {
+        let occupied_ids: HashSet<i32> = seats.iter().map(|s| s.id).collect();
+        for r in 1..=126 {
+            for c in 0..=7 {
+                let test_id = seat_id(r, c);
+                if !occupied_ids.contains(&test_id)
+                    && occupied_ids.contains(&(test_id - 1))
+                    && occupied_ids.contains(&(test_id + 1))
+                {
+                    log::info!("Found empty seat: {} {} => {}", r, c, test_id);
+                    return Ok(test_id);
+                }
+            }
+        }
+        Err("no empty seat found".into())
+    }
+}
+
+/// Decodes a boarding pass like `FBFBBFFRLR`, binary partitioning the rows
+/// then the columns.
+pub fn parse_seat(l: &str) -> std::result::Result<Seat, ParseError> {
+    if l.chars().count() != 10 {
+        return Err(ParseError::at(l, l, "a boarding pass of 10 characters"));
+    }
+
+    let row = {
+        let mut r1 = 0;
+        let mut r2 = 127;
+        for (i, v) in l.char_indices().take(7) {
+            let mid = (r1 + r2) / 2;
+            let ofs = (r1 + r2) % 2;
+            if v == 'F' {
+                // lower half
+                r2 = mid;
+            } else if v == 'B' {
+                // upper half
+                r1 = mid + ofs;
+            } else {
+                return Err(ParseError::at(l, &l[i..], "'F' or 'B'"));
+            }
+            log::trace!("instruction {} -> {} {}", v, r1, r2);
+        }
+        r1
+    };
+
+    let col = {
+        let mut c1 = 0;
+        let mut c2 = 7;
+        for (i, v) in l.char_indices().skip(7).take(3) {
+            let mid = (c1 + c2) / 2;
+            let ofs = (c1 + c2) % 2;
+            if v == 'L' {
+                // lower half
+                c2 = mid;
+            } else if v == 'R' {
+                // upper half
+                c1 = mid + ofs;
+            } else {
+                return Err(ParseError::at(l, &l[i..], "'L' or 'R'"));
+            }
+        }
+        c1
+    };
+
+    Ok(Seat {
+        row,
+        col,
+        id: seat_id(row, col),
+    })
+}
+
+pub fn seat_id(row: i32, col: i32) -> i32 {
+    row * 8 + col
+}
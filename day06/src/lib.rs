@@ -0,0 +1,83 @@
+use common::{ParseError, Result, Solution};
+use std::collections::HashSet;
+
+/// The answers of each group, a line per person; groups are separated by
+/// blank lines, and a run of them counts as one.
+pub fn parse_input(input: &str) -> std::result::Result<Vec<Vec<String>>, ParseError> {
+    let input = &common::parse::normalize(input);
+    let mut groups: Vec<Vec<String>> = vec![Vec::new()];
+    for line in input.lines() {
+        if line.is_empty() {
+            if groups.last().is_some_and(|g| !g.is_empty()) {
+                groups.push(Vec::new())
+            }
+        } else if let Some(i) = line.find(|c: char| !c.is_ascii_lowercase()) {
+            return Err(ParseError::at(
+                input,
+                &line[i..],
+                "a question from 'a' to 'z'",
+            ));
+        } else {
+            groups.last_mut().unwrap().push(line.to_string());
+        }
+    }
+    Ok(groups)
+}
+
+/// Questions anyone in the group answered yes to.
+pub fn anyone_count(group: &[String]) -> usize {
+    let found_chars = group.iter().fold(HashSet::new(), |mut set, l| {
+        for c in l.chars() {
+            set.insert(c);
+        }
+        set
+    });
+    found_chars.len()
+}
+
+/// Questions everyone in the group answered yes to.
+pub fn everyone_count(group: &[String]) -> usize {
+    let mut sets_iter = group.iter().map(|l| l.chars().collect::<HashSet<char>>());
+    // let intersection = sets_iter.fold(None, |acc_option, set| match acc_option {
+    //     None => Some(set.clone()),
+    //     Some(acc) => Some(&acc & &set),
+    // });
+
+    // very smart implementation by taking first element
+    let intersection = sets_iter
+        .next()
+        .map(|s| sets_iter.fold(s, |acc, set| &acc & &set));
+    match intersection {
+        None => 0,
+        Some(ii) => ii.len(),
+    }
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Vec<Vec<String>>;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Vec<String>>> {
+        Ok(parse_input(input)?)
+    }
+
+    // part 1 -- which questions *anyone* answered yes to in a group
+    fn part1(groups: &Vec<Vec<String>>) -> Result<usize> {
+        let counts: Vec<_> = groups.iter().map(|g| anyone_count(g)).collect();
+
+        log::debug!("Part1 -> Counts: {:?}", counts);
+        Ok(counts.iter().sum())
+    }
+
+    // part 2 -- which questions *everyone* in a group answered yes to
+    fn part2(groups: &Vec<Vec<String>>) -> Result<usize> {
+        let counts_everyone: Vec<_> = groups.iter().map(|g| everyone_count(g)).collect();
+
+        log::debug!("Part2 -> Counts: {:?}", counts_everyone);
+        Ok(counts_everyone.iter().sum())
+    }
+}
@@ -1,70 +1,12 @@
-use std::{
-    collections::HashSet,
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use common::Solution;
+use day06::Day06;
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let file = File::open("input.txt")?;
-    let buffered = BufReader::new(file);
+fn main() -> common::Result<()> {
+    common::logging::init_from_env()?;
+    let groups = common::input::parse_from_args::<Day06>(6)?;
 
-    let mut groups: Vec<Vec<String>> = Vec::new();
-    groups.push(Vec::new());
-    for l in buffered.lines() {
-        let line = l?;
-        if line.is_empty() {
-            groups.push(Vec::new())
-        } else {
-            groups.last_mut().unwrap().push(line);
-        }
-    }
-
-    // part 1 -- which questions *anyone* answered yes to in a group
-    let counts: Vec<_> = groups
-        .iter()
-        .map(|g| {
-            let found_chars = g.iter().fold(HashSet::new(), |mut set, l| {
-                for c in l.chars() {
-                    set.insert(c);
-                }
-                set
-            });
-            found_chars.len()
-        })
-        .collect();
-
-    println!(
-        "Part1 -> Counts: {:?}, sum: {}",
-        counts,
-        counts.iter().sum::<usize>()
-    );
-
-    // part 2 -- which questions *everyone* in a group answered yes to
-    let counts_everyone: Vec<_> = groups
-        .iter()
-        .map(|g| {
-            let mut sets_iter = g.iter().map(|l| l.chars().collect::<HashSet<char>>());
-            // let intersection = sets_iter.fold(None, |acc_option, set| match acc_option {
-            //     None => Some(set.clone()),
-            //     Some(acc) => Some(&acc & &set),
-            // });
-            
-            // very smart implementation by taking first element
-            let intersection = sets_iter
-                .next()
-                .map(|s| sets_iter.fold(s, |acc, set| &acc & &set));
-            match intersection {
-                None => 0,
-                Some(ii) => ii.len(),
-            }
-        })
-        .collect();
-    println!(
-        "Part2 -> Counts: {:?}, sum: {}",
-        counts_everyone,
-        counts_everyone.iter().sum::<usize>()
-    );
+    println!("Part1 -> sum: {}", Day06::part1(&groups)?);
+    println!("Part2 -> sum: {}", Day06::part2(&groups)?);
 
     Ok(())
 }
@@ -0,0 +1,189 @@
+// Bag rules that never loop: a bag only ever contains bags later in a
+// random order. About half the bags before mine are picked to hold it, each
+// directly or through another of them, and no other bag may.
+
+use std::{cmp::Ordering, ops::RangeInclusive};
+
+use generate::{Generate, Generated, Rng};
+
+use crate::{Day07, MY_BAG};
+
+const SHADES: [&str; 18] = [
+    "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light", "mirrored",
+    "muted", "pale", "plaid", "posh", "shiny", "striped", "vibrant", "wavy",
+];
+const COLOURS: [&str; 33] = [
+    "aqua",
+    "beige",
+    "black",
+    "blue",
+    "bronze",
+    "brown",
+    "chartreuse",
+    "coral",
+    "crimson",
+    "cyan",
+    "fuchsia",
+    "gold",
+    "gray",
+    "green",
+    "indigo",
+    "lavender",
+    "lime",
+    "magenta",
+    "maroon",
+    "olive",
+    "orange",
+    "plum",
+    "purple",
+    "red",
+    "salmon",
+    "silver",
+    "tan",
+    "teal",
+    "tomato",
+    "turquoise",
+    "violet",
+    "white",
+    "yellow",
+];
+// keeps the bag counts well inside an i32, with room in each bag for any of
+// the bags after it
+const MAX_INSIDE: i64 = 1_000_000;
+const MAX_INSIDE_MINE: i64 = 10_000;
+const MAX_INSIDE_AFTER_MINE: i64 = 1_000;
+
+impl Generate for Day07 {
+    const SIZE_OF: &'static str = "bag colours";
+    const DEFAULT_SIZE: usize = SHADES.len() * COLOURS.len();
+    const SIZES: RangeInclusive<usize> = 2..=SHADES.len() * COLOURS.len();
+
+    fn valid(rng: &mut Rng, size: usize) -> Generated {
+        let mut bags: Vec<String> = SHADES
+            .iter()
+            .flat_map(|s| COLOURS.iter().map(move |c| format!("{} {}", s, c)))
+            .filter(|b| b != MY_BAG)
+            .collect();
+        rng.shuffle(&mut bags);
+        bags.truncate(size - 1);
+        // somewhere in the middle, so there are bags both outside and inside
+        let mine = size / 4 + rng.usize(..=size / 2);
+        bags.insert(mine, MY_BAG.to_string());
+
+        let holders: Vec<bool> = (0..size).map(|b| b < mine && rng.bool()).collect();
+
+        // from the last bag back, so the bags inside are counted first
+        let mut contains: Vec<Vec<(i64, usize)>> = vec![Vec::new(); size];
+        let mut inside = vec![0i64; size];
+        for outer in (0..size - 1).rev() {
+            let limit = match outer.cmp(&mine) {
+                Ordering::Less => MAX_INSIDE,
+                Ordering::Equal => MAX_INSIDE_MINE,
+                Ordering::Greater => MAX_INSIDE_AFTER_MINE,
+            };
+            let mut total = 0;
+            if holders[outer] {
+                let held: Vec<usize> = (outer + 1..=mine)
+                    .filter(|&b| b == mine || holders[b])
+                    .collect();
+                let inner = held[rng.usize(..held.len())];
+                total += 1 + inside[inner];
+                contains[outer].push((1, inner));
+            }
+            // mine holds something, so part 2 isn't trivially zero
+            let picks = match outer == mine {
+                true => rng.usize(1..=3),
+                false => rng.usize(..=3),
+            };
+            for _ in 0..picks {
+                let inner = rng.usize(outer + 1..size);
+                let number = rng.i64(1..=5);
+                let more = number * (1 + inside[inner]);
+                let holds_mine = inner == mine || holders[inner];
+                if (holds_mine && !holders[outer])
+                    || contains[outer].iter().any(|&(_, b)| b == inner)
+                    || total + more > limit
+                {
+                    continue;
+                }
+                total += more;
+                contains[outer].push((number, inner));
+            }
+            inside[outer] = total;
+        }
+
+        let mut rules: Vec<String> = (0..size)
+            .map(|outer| {
+                let inner: Vec<String> = contains[outer]
+                    .iter()
+                    .map(|&(number, inner)| {
+                        let plural = if number == 1 { "" } else { "s" };
+                        format!("{} {} bag{}", number, bags[inner], plural)
+                    })
+                    .collect();
+                let inner = match inner.is_empty() {
+                    true => "no other bags".to_string(),
+                    false => inner.join(", "),
+                };
+                format!("{} bags contain {}.", bags[outer], inner)
+            })
+            .collect();
+        rng.shuffle(&mut rules);
+
+        Generated::new(rules.join("\n") + "\n")
+            .with_part1(holders.iter().filter(|&&h| h).count())
+            .with_part2(inside[mine])
+    }
+
+    fn corrupt(rng: &mut Rng, line: &str) -> String {
+        match rng.bool() {
+            true => line.replacen(" bags contain ", " bags hold ", 1),
+            false => line[..1].to_uppercase() + &line[1..],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+    use generate::{generate, Validity};
+
+    #[test]
+    fn generated_rules_have_the_expected_answers() {
+        for seed in 0..10 {
+            let generated = generate::<Day07>(seed, 100, Validity::Valid).unwrap();
+            let rules = Day07::parse(&generated.input).unwrap();
+            assert_eq!(rules.len(), 100);
+            assert_ne!(generated.part1.as_deref(), Some("0"));
+            assert_ne!(generated.part2.as_deref(), Some("0"));
+            assert_eq!(
+                Some(Day07::part1(&rules).unwrap().to_string()),
+                generated.part1
+            );
+            assert_eq!(
+                Some(Day07::part2(&rules).unwrap().to_string()),
+                generated.part2
+            );
+        }
+    }
+
+    #[test]
+    fn corrupted_rules_fail_to_parse() {
+        for seed in 0..20 {
+            let generated = generate::<Day07>(seed, 20, Validity::Invalid).unwrap();
+            assert!(
+                Day07::parse(&generated.input).is_err(),
+                "{}",
+                generated.input
+            );
+        }
+    }
+
+    #[test]
+    fn seeds_are_reproducible() {
+        let input = |seed| generate::<Day07>(seed, 20, Validity::Valid).unwrap().input;
+        assert_eq!(input(1), input(1));
+        assert_ne!(input(1), input(2));
+    }
+}
@@ -0,0 +1,201 @@
+use std::{collections::HashSet, hash::Hash};
+
+use common::{parse, ParseError, Result, Solution};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+mod generator;
+
+/// The bag the puzzle asks about.
+pub const MY_BAG: &str = "shiny gold";
+
+/// The puzzle constants, as in the puzzle unless changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    /// The colour of the bag to ask about.
+    pub bag: String,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            bag: MY_BAG.to_string(),
+        }
+    }
+}
+
+/// A bag colour, e.g. `shiny gold`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct Bag(pub String);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BagQuantity {
+    pub number: i32,
+    pub bag: Bag,
+}
+
+/// What a bag must contain.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BagRule {
+    pub bag: Bag,
+    pub contains: Vec<BagQuantity>,
+}
+
+// recursively find bags that can contain this bag
+pub fn containers_for(bag: &Bag, rules: &[BagRule]) -> HashSet<Bag> {
+    let mut set = HashSet::new();
+    for rule in rules
+        .iter()
+        .filter(|&r| r.contains.iter().any(|qty| &qty.bag == bag))
+    {
+        // direct container of this bag
+        set.insert(rule.bag.clone());
+        // containers of this container (recursive)
+        for b in containers_for(&rule.bag, rules) {
+            set.insert(b);
+        }
+    }
+    set
+}
+
+// recursively count the bags inside a given bag
+pub fn bags_inside(bag: &Bag, rules: &[BagRule]) -> i32 {
+    let rule = rules.iter().find(|&r| &r.bag == bag).expect("missing rule");
+    let mut sum = 1; // this bag
+    for bq in rule.contains.iter() {
+        sum += bq.number * bags_inside(&bq.bag, rules);
+    }
+    sum
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<BagRule>> {
+    let input = &parse::normalize(input);
+    // try to change this to use nom -- would be much cooler
+    let regex_bag_qty = Regex::new("^(\\d+) ([a-z ]+?) bags?\\.?$")?;
+
+    let rules = parse::lines(input, |line| {
+        // vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+        let (outer, inner) = line.split_once(" bags contain ").ok_or_else(|| {
+            ParseError::at(line, line, "a rule like \"<colour> bags contain ...\"")
+        })?;
+
+        // left hand side -- bag
+        if let Some(i) = outer.find(|c: char| !c.is_ascii_lowercase() && c != ' ') {
+            return Err(ParseError::at(line, &outer[i..], "a bag colour"));
+        }
+        let bag = Bag(outer.trim().to_string());
+
+        // right hand side -- list of bags it contains
+        let mut bag_quantities = Vec::new();
+        let mut colours = Vec::new();
+        if inner != "no other bags." {
+            for contained in inner.split(',').map(str::trim) {
+                let captures = regex_bag_qty.captures(contained).ok_or_else(|| {
+                    ParseError::at(
+                        line,
+                        contained,
+                        "a number of bags, like \"2 dark red bags\"",
+                    )
+                })?;
+                let colour = captures.get(2).unwrap().as_str();
+                colours.push(colour);
+                bag_quantities.push(BagQuantity {
+                    bag: Bag(colour.into()),
+                    number: parse::number(line, captures.get(1).unwrap().as_str())?,
+                })
+            }
+        }
+
+        let rule = BagRule {
+            bag,
+            contains: bag_quantities,
+        };
+        log::trace!("Rule: {:?}", rule);
+        Ok((rule, colours))
+    })?;
+
+    // every bag inside another needs a rule of its own
+    let defined: HashSet<&Bag> = rules.iter().map(|(r, _)| &r.bag).collect();
+    for (rule, colours) in rules.iter() {
+        for (qty, colour) in rule.contains.iter().zip(colours) {
+            if !defined.contains(&qty.bag) {
+                return Err(ParseError::at(input, colour, "a bag colour with a rule").into());
+            }
+        }
+    }
+    Ok(rules.into_iter().map(|(r, _)| r).collect())
+}
+
+// the bag to ask about, which has to have a rule of its own
+fn my_bag(rules: &[BagRule], params: &Params) -> Result<Bag> {
+    let bag = Bag(params.bag.clone());
+    match rules.iter().any(|r| r.bag == bag) {
+        true => Ok(bag),
+        false => Err(format!("no rule for {} bags", params.bag).into()),
+    }
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<BagRule>;
+    type Params = Params;
+    type Part1 = usize;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<BagRule>> {
+        parse_input(input)
+    }
+
+    fn validate(rules: &Vec<BagRule>) -> Result<()> {
+        let defined: HashSet<&Bag> = rules.iter().map(|r| &r.bag).collect();
+        let inside = rules.iter().flat_map(|r| r.contains.iter());
+        match inside.map(|q| &q.bag).find(|b| !defined.contains(b)) {
+            Some(bag) => Err(format!("no rule for {} bags", bag.0).into()),
+            None => Ok(()),
+        }
+    }
+
+    fn part1(rules: &Vec<BagRule>) -> Result<usize> {
+        Self::part1_with(rules, &Params::default())
+    }
+
+    fn part2(rules: &Vec<BagRule>) -> Result<i32> {
+        Self::part2_with(rules, &Params::default())
+    }
+
+    // part 1 -- find out what bags can eventually hold my bag
+    fn part1_with(rules: &Vec<BagRule>, params: &Params) -> Result<usize> {
+        let my_bag = my_bag(rules, params)?;
+        let containers = containers_for(&my_bag, rules);
+        log::debug!("Part 1 -> Containers: {:?}", containers);
+        Ok(containers.len())
+    }
+
+    // part 2 -- find out how many total bags a single bag contains
+    fn part2_with(rules: &Vec<BagRule>, params: &Params) -> Result<i32> {
+        let my_bag = my_bag(rules, params)?;
+        let contained_bags = bags_inside(&my_bag, rules);
+        // less this bag
+        Ok(contained_bags - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undefined_bag_located() {
+        let input = "light red bags contain 1 bright white bag.\n\
+                     bright white bags contain 2 dull blue bags, 1 shiny gold bag.\n\
+                     shiny gold bags contain no other bags.\n";
+        let err = Day07::parse(input).err().unwrap();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!(
+            (2, 29, "dull blue"),
+            (err.line, err.column, err.found.as_str())
+        );
+    }
+}
@@ -0,0 +1,160 @@
+// Boot code that runs straight through to the end, with one forward `jmp`
+// turned into a `nop` so that it loops instead.
+//
+// Every `jmp` on the way to the end skips over a few instructions that all
+// jump back to earlier ones; the corrupted `nop` falls into them. The `nop`s
+// on the way jump back to earlier instructions (or to themselves) if they
+// are flipped, and a flipped `jmp` falls into what it skipped, so only
+// flipping the corrupted instruction back makes the program terminate.
+
+use std::ops::RangeInclusive;
+
+use generate::{Generate, Generated, Rng};
+
+use crate::{Day08, Instruction};
+
+fn render(instruction: &Instruction) -> String {
+    match instruction {
+        Instruction::Nop(x) => format!("nop {:+}", x),
+        Instruction::Acc(x) => format!("acc {:+}", x),
+        Instruction::Jmp(x) => format!("jmp {:+}", x),
+    }
+}
+
+impl Generate for Day08 {
+    const SIZE_OF: &'static str = "instructions";
+    const DEFAULT_SIZE: usize = 650;
+    // room for at least one jump over two instructions
+    const SIZES: RangeInclusive<usize> = 4..=usize::MAX;
+
+    fn valid(rng: &mut Rng, size: usize) -> Generated {
+        loop {
+            let mut program: Vec<Option<Instruction>> = vec![None; size];
+            // the instructions run, with the accumulator before each of them
+            let mut path: Vec<(usize, i32)> = Vec::new();
+            let mut jumps = Vec::new();
+
+            let mut pc = 0;
+            let mut acc = 0;
+            while pc < size {
+                path.push((pc, acc));
+                let skip = rng.usize(2..=5);
+                let instruction = match rng.usize(..3) {
+                    0 if pc + skip < size => {
+                        for (trap, slot) in program.iter_mut().enumerate().skip(pc + 1).take(skip) {
+                            let (target, _) = path[rng.usize(..path.len())];
+                            *slot = Some(Instruction::Jmp(target as i32 - trap as i32));
+                        }
+                        jumps.push(pc);
+                        Instruction::Jmp(skip as i32 + 1)
+                    }
+                    1 => {
+                        let (target, _) = path[rng.usize(..path.len())];
+                        Instruction::Nop(target as i32 - pc as i32)
+                    }
+                    _ => Instruction::Acc(rng.i32(-50..=50)),
+                };
+                program[pc] = Some(instruction.clone());
+                match instruction {
+                    Instruction::Jmp(x) => pc += x as usize,
+                    Instruction::Acc(x) => {
+                        acc += x;
+                        pc += 1;
+                    }
+                    Instruction::Nop(_) => pc += 1,
+                }
+            }
+
+            // try again if there was never room for a jump
+            if jumps.is_empty() {
+                continue;
+            }
+            let corrupted = jumps[rng.usize(..jumps.len())];
+            let (_, acc_before) = path.iter().find(|&&(at, _)| at == corrupted).unwrap();
+            if let Some(Instruction::Jmp(x)) = program[corrupted] {
+                program[corrupted] = Some(Instruction::Nop(x));
+            }
+
+            let text: Vec<String> = program.iter().flatten().map(render).collect();
+            return Generated::new(text.join("\n") + "\n")
+                .with_part1(acc_before)
+                .with_part2(acc);
+        }
+    }
+
+    fn corrupt(rng: &mut Rng, line: &str) -> String {
+        match rng.bool() {
+            true => line.replacen(' ', "", 1),
+            false => line.replacen(&line[..3], "jnz", 1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_program;
+    use common::Solution;
+    use generate::{generate, Validity};
+
+    fn flip(instruction: &Instruction) -> Instruction {
+        match *instruction {
+            Instruction::Nop(x) => Instruction::Jmp(x),
+            Instruction::Jmp(x) => Instruction::Nop(x),
+            Instruction::Acc(x) => Instruction::Acc(x),
+        }
+    }
+
+    #[test]
+    fn generated_programs_have_the_expected_answers() {
+        for seed in 0..10 {
+            let generated = generate::<Day08>(seed, 200, Validity::Valid).unwrap();
+            let program = Day08::parse(&generated.input).unwrap();
+            assert_eq!(program.len(), 200);
+            assert_eq!(
+                Some(Day08::part1(&program).unwrap().to_string()),
+                generated.part1
+            );
+            assert_eq!(
+                Some(Day08::part2(&program).unwrap().to_string()),
+                generated.part2
+            );
+        }
+    }
+
+    #[test]
+    fn exactly_one_flip_fixes_the_program() {
+        for seed in 0..20 {
+            let generated = generate::<Day08>(seed, 60, Validity::Valid).unwrap();
+            let program = Day08::parse(&generated.input).unwrap();
+            assert_ne!(run_program(&program).pc, program.len());
+            let fixes = (0..program.len())
+                .filter(|&i| {
+                    let mut fixed = program.clone();
+                    fixed[i] = flip(&program[i]);
+                    run_program(&fixed).pc == program.len()
+                })
+                .count();
+            assert_eq!(fixes, 1, "{}", generated.input);
+        }
+    }
+
+    #[test]
+    fn corrupted_programs_fail_to_parse() {
+        for seed in 0..20 {
+            let generated = generate::<Day08>(seed, 20, Validity::Invalid).unwrap();
+            assert!(
+                Day08::parse(&generated.input).is_err(),
+                "{}",
+                generated.input
+            );
+        }
+    }
+
+    #[test]
+    fn seeds_are_reproducible() {
+        let input = |seed| generate::<Day08>(seed, 20, Validity::Valid).unwrap().input;
+        assert_eq!(input(1), input(1));
+        assert_ne!(input(1), input(2));
+    }
+}
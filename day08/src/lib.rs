@@ -0,0 +1,116 @@
+use common::{parse, ParseError, Result, Solution};
+use serde::{Deserialize, Serialize};
+
+mod generator;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Instruction {
+    Nop(i32),
+    Acc(i32),
+    Jmp(i32),
+}
+
+pub fn parse_input(input: &str) -> std::result::Result<Vec<Instruction>, ParseError> {
+    let input = &parse::normalize(input);
+    let instructions = parse::lines(input, |line| {
+        let (inst, arg) = line
+            .split_once(' ')
+            .ok_or_else(|| ParseError::at_end(line, "an instruction argument"))?;
+        let arg = parse::number(line, arg)?;
+
+        match inst {
+            "nop" => Ok(Instruction::Nop(arg)),
+            "acc" => Ok(Instruction::Acc(arg)),
+            "jmp" => Ok(Instruction::Jmp(arg)),
+            _ => Err(ParseError::at(line, inst, "\"nop\", \"acc\" or \"jmp\"")),
+        }
+    })?;
+
+    log::trace!("Instructions: {:?}", instructions);
+    Ok(instructions)
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Vec<Instruction>;
+    type Params = ();
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<Instruction>> {
+        Ok(parse_input(input)?)
+    }
+
+    // part 1 -- find completed state for example program
+    fn part1(instructions: &Vec<Instruction>) -> Result<i32> {
+        let part1_state = run_program(instructions);
+        log::info!("Part 1 -> Completed with state {:?}", part1_state);
+        Ok(part1_state.acc)
+    }
+
+    // part 2 -- find the mutated program for which the terminal state is pc == instructions.len
+    //           i.e. the next instruction after the end of the program
+    fn part2(instructions: &Vec<Instruction>) -> Result<i32> {
+        for (mutate_index, original_instruction) in instructions.iter().enumerate() {
+            let mut mutated = instructions.clone();
+            mutated[mutate_index] = match original_instruction {
+                Instruction::Nop(x) => Instruction::Jmp(*x),
+                Instruction::Jmp(x) => Instruction::Nop(*x),
+                Instruction::Acc(x) => Instruction::Acc(*x),
+            };
+
+            // run the mutated program
+            let terminal_state = run_program(&mutated);
+            if terminal_state.pc == instructions.len() {
+                log::info!(
+                    "Found a working program. Terminated with {:?}",
+                    terminal_state
+                );
+                return Ok(terminal_state.acc);
+            }
+        }
+        Err("no working program found".into())
+    }
+}
+
+/// The program counter and accumulator.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct State {
+    pub pc: usize,
+    pub acc: i32,
+}
+impl State {
+    pub fn next(&self, instruction: &Instruction) -> Self {
+        match instruction {
+            Instruction::Nop(_) => State {
+                pc: self.pc + 1,
+                ..*self
+            },
+            Instruction::Acc(x) => State {
+                pc: self.pc + 1,
+                acc: self.acc + x,
+            },
+            Instruction::Jmp(x) => State {
+                pc: (self.pc as i32 + x) as usize,
+                ..*self
+            },
+        }
+    }
+}
+
+/// Runs until the program ends (`pc` just past the last instruction) or is
+/// about to repeat an instruction, and returns the state at that point.
+pub fn run_program(instructions: &[Instruction]) -> State {
+    let mut visited = vec![false; instructions.len()];
+    let mut state = State { pc: 0, acc: 0 };
+
+    while state.pc < instructions.len() && !visited[state.pc] {
+        let instruction = &instructions[state.pc];
+        visited[state.pc] = true;
+        //println!("state {:?} next instruction {:?}", state, instruction);
+        state = state.next(instruction);
+    }
+
+    state
+}
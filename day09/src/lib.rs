@@ -0,0 +1,141 @@
+use common::{parse, Result, Solution};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+pub const PREAMBLE: usize = 25; // 'example-input.txt' uses 5, see its params
+
+/// The puzzle constants, as in the puzzle unless changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    /// How many numbers each number is checked against.
+    pub preamble: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { preamble: PREAMBLE }
+    }
+}
+
+/// A number checked against the preamble before it.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum Evaluation {
+    Preamble,
+    Valid(i64),
+    Invalid(i64),
+}
+
+/// Whether `value` is the sum of two different numbers in `buffer`.
+pub fn number_is_valid(value: i64, buffer: &VecDeque<i64>) -> bool {
+    for i in buffer.iter() {
+        for j in buffer.iter() {
+            if i != j && i + j == value {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// The first run of at least two numbers that sums to `target`.
+pub fn find_contiguous_slice(target: i64, numbers: &[i64]) -> Option<&[i64]> {
+    for i0 in 0..numbers.len() - 1 {
+        for i1 in i0 + 1..numbers.len() {
+            let slice = &numbers[i0..i1];
+            let mut acc = 0;
+            // early breakout
+            for v in slice {
+                acc += v;
+                if acc > target {
+                    break;
+                }
+                if acc == target {
+                    return Some(slice);
+                }
+            }
+            // if slice.iter().sum::<i64>() == target {
+            //     return Some(slice);
+            // }
+        }
+    }
+    None
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<i64>> {
+    let input = &parse::normalize(input);
+    Ok(parse::lines(input, |l| parse::number(l, l))?)
+}
+
+/// The first number that isn't the sum of two of the `preamble` numbers
+/// before it.
+pub fn first_invalid(numbers: &[i64], preamble: usize) -> Option<i64> {
+    let eval = numbers.iter().scan(VecDeque::new(), |buffer, value| {
+        let evaluation = {
+            if buffer.len() < preamble {
+                Evaluation::Preamble
+            } else if number_is_valid(*value, buffer) {
+                Evaluation::Valid(*value)
+            } else {
+                Evaluation::Invalid(*value)
+            }
+        };
+
+        // push new value onto the buffer, and pop the old value off when
+        // we're at the correct capacity
+        buffer.push_back(*value);
+        if buffer.len() > preamble {
+            buffer.pop_front();
+        }
+
+        Some(evaluation)
+    });
+
+    let results: Vec<_> = eval.collect();
+    log::trace!("results {:?}", results);
+    results.iter().find_map(|v| {
+        if let Evaluation::Invalid(x) = v {
+            Some(*x)
+        } else {
+            None
+        }
+    })
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<i64>;
+    type Params = Params;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<i64>> {
+        parse_input(input)
+    }
+
+    fn part1(numbers: &Vec<i64>) -> Result<i64> {
+        Self::part1_with(numbers, &Params::default())
+    }
+
+    fn part2(numbers: &Vec<i64>) -> Result<i64> {
+        Self::part2_with(numbers, &Params::default())
+    }
+
+    // part 1
+    fn part1_with(numbers: &Vec<i64>, params: &Params) -> Result<i64> {
+        let invalid = first_invalid(numbers, params.preamble).ok_or("no invalid number found")?;
+        Ok(invalid)
+    }
+
+    // part 2
+    // now find contiguous range that adds up to the first invalid
+    fn part2_with(numbers: &Vec<i64>, params: &Params) -> Result<i64> {
+        let invalid = first_invalid(numbers, params.preamble).ok_or("no invalid number found")?;
+        let sl = find_contiguous_slice(invalid, numbers).ok_or("no contiguous slice found")?;
+        log::info!("Found slice: {:?}", sl);
+        let min = sl.iter().min().unwrap();
+        let max = sl.iter().max().unwrap();
+        Ok(min + max)
+    }
+}
@@ -0,0 +1,241 @@
+use common::{parse, ParseError, Result, Solution};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// The bag of adapters, and the device's built-in adapter 3 jolts above the
+/// highest of them.
+#[derive(Serialize, Deserialize)]
+pub struct AdaptersProblem {
+    adapters: Vec<i32>,
+    final_joltage: i32,
+}
+impl AdaptersProblem {
+    pub fn create(adapters: Vec<i32>) -> Self {
+        let final_joltage = *adapters.iter().max().unwrap() + 3;
+        AdaptersProblem {
+            adapters,
+            final_joltage,
+        }
+    }
+
+    pub fn adapters(&self) -> &[i32] {
+        &self.adapters
+    }
+
+    pub fn final_joltage(&self) -> i32 {
+        self.final_joltage
+    }
+
+    /// A chain from the outlet (0) to the device using every adapter.
+    pub fn find_chain(&self) -> Option<Vec<i32>> {
+        let bag: HashSet<i32> = self.adapters.iter().copied().collect();
+        self.find_chain_internal(vec![0], bag)
+    }
+
+    // need to use all the adapters
+    fn find_chain_internal(&self, chain: Vec<i32>, bag: HashSet<i32>) -> Option<Vec<i32>> {
+        if bag.is_empty() {
+            let mut new_chain = chain.clone();
+            new_chain.push(self.final_joltage);
+            Some(new_chain)
+        } else {
+            // find all adapters that can connect to the start
+            let start = chain.last().unwrap();
+            let mut viable_adapters: Vec<_> = bag
+                .iter()
+                .filter(|&&adapt| {
+                    let diff = adapt - start;
+                    (1..=3).contains(&diff)
+                })
+                .collect();
+            // now sort, and pick the smallest item first (greedy)
+            viable_adapters.sort();
+            // recursively consider options
+            viable_adapters.iter().find_map(|&adapt| {
+                let mut new_chain = chain.clone();
+                let mut new_bag = bag.clone();
+                new_chain.push(*adapt);
+                new_bag.remove(adapt);
+                self.find_chain_internal(new_chain, new_bag)
+            })
+        }
+    }
+
+    /// The number of distinct chains from the outlet to the device.
+    pub fn count_chains(&self) -> i64 {
+        let mut bag: Vec<i32> = self.adapters.to_vec();
+        bag.sort();
+        let mut cache = HashMap::new();
+        self.count_chains_internal(0, 0, &bag, &mut cache)
+    }
+
+    // need to use the adapters to reach the target voltage; not necessary to use all of them
+    fn count_chains_internal(
+        &self,
+        last: i32,
+        start_index: usize,
+        bag: &[i32],
+        cache: &mut HashMap<i32, i64>,
+    ) -> i64 {
+        if last + 3 == self.final_joltage {
+            // we've hit our target; this chain terminates valid
+            1
+        } else {
+            let mut sum = 0i64;
+            for current in start_index..bag.len() {
+                let adapt = bag[current];
+                let diff = adapt - last;
+                // if diff < 1 {
+                //     // check
+                //     panic!("diff {} == {} - {}", diff, adapt, last);
+                // }
+                if diff <= 3 {
+                    // recursively count viable options, checking the cache first
+                    // simply return the result from the cache if available: we've already calculated it
+                    if let Some(cached) = cache.get(&adapt) {
+                        sum += *cached;
+                    } else {
+                        let count = self.count_chains_internal(adapt, current + 1, bag, cache);
+                        cache.insert(adapt, count);
+                        sum += count;
+                    }
+                } else {
+                    // no use considering higher values -- they're all unviable
+                    break;
+                }
+            }
+            sum
+        }
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<AdaptersProblem> {
+    let input = &parse::normalize(input);
+    let adapters = parse::lines(input, |l| parse::number(l, l))?;
+    if adapters.is_empty() {
+        return Err(ParseError::at_end(input, "at least one adapter").into());
+    }
+    Ok(AdaptersProblem::create(adapters))
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = AdaptersProblem;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<AdaptersProblem> {
+        parse_input(input)
+    }
+
+    fn validate(problem: &AdaptersProblem) -> Result<()> {
+        match problem.adapters.iter().max() {
+            None => Err("no adapters".into()),
+            Some(max) if problem.final_joltage != max + 3 => Err(format!(
+                "the device is rated {} jolts, not 3 above the highest adapter",
+                problem.final_joltage
+            )
+            .into()),
+            Some(_) => Ok(()),
+        }
+    }
+
+    // part 1 -- product of the 1-jolt and 3-jolt differences in the full chain
+    fn part1(problem: &AdaptersProblem) -> Result<usize> {
+        let chain = problem.find_chain().ok_or("no chain found")?;
+        log::debug!("Chain: {:?}", chain);
+
+        let diffs: Vec<_> = chain
+            .iter()
+            .zip(chain.iter().skip(1))
+            .map(|(&a, &b)| b - a)
+            .collect();
+        log::debug!("Diffs: {:?}", diffs);
+
+        let diffs_1 = diffs.iter().filter(|&&v| v == 1).count();
+        let diffs_3 = diffs.iter().filter(|&&v| v == 3).count();
+        log::info!("n(1): {}, n(3): {}", diffs_1, diffs_3);
+        Ok(diffs_1 * diffs_3)
+    }
+
+    // part 2 -- number of possible chains
+    fn part2(problem: &AdaptersProblem) -> Result<i64> {
+        Ok(problem.count_chains())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // every subset of the adapters that forms a chain, one at a time
+    fn count_chains_exhaustively(problem: &AdaptersProblem) -> i64 {
+        let mut bag = problem.adapters().to_vec();
+        bag.sort();
+        (0..1u32 << bag.len())
+            .filter(|subset| {
+                let chain = bag
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| subset & (1 << i) != 0)
+                    .map(|(_, &a)| a);
+                let mut last = 0;
+                for adapter in chain.chain(std::iter::once(problem.final_joltage())) {
+                    if !(1..=3).contains(&(adapter - last)) {
+                        return false;
+                    }
+                    last = adapter;
+                }
+                true
+            })
+            .count() as i64
+    }
+
+    // distinct adapters, built up from gaps; a gap of 4 leaves no chains at all
+    fn adapters(max_len: usize) -> impl Strategy<Value = Vec<i32>> {
+        prop::collection::vec(1..=4i32, 1..=max_len)
+            .prop_map(|gaps| {
+                gaps.iter()
+                    .scan(0, |joltage, gap| {
+                        *joltage += gap;
+                        Some(*joltage)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .prop_shuffle()
+    }
+
+    #[test]
+    fn no_adapters_rejected() {
+        let err = Day10::parse("\n").err().unwrap();
+        let err = err.downcast::<ParseError>().unwrap();
+        assert_eq!("at least one adapter", err.expected);
+    }
+
+    proptest! {
+        #[test]
+        fn chain_counts_match_enumeration(adapters in adapters(14)) {
+            let problem = AdaptersProblem::create(adapters);
+            prop_assert_eq!(problem.count_chains(), count_chains_exhaustively(&problem));
+        }
+
+        #[test]
+        fn full_chain_is_the_sorted_adapters(adapters in adapters(40)) {
+            let problem = AdaptersProblem::create(adapters.clone());
+            let mut sorted = adapters;
+            sorted.sort();
+            let expected = if sorted.windows(2).all(|w| w[1] - w[0] <= 3) && sorted[0] <= 3 {
+                let mut chain = vec![0];
+                chain.extend(&sorted);
+                chain.push(problem.final_joltage());
+                Some(chain)
+            } else {
+                None
+            };
+            prop_assert_eq!(problem.find_chain(), expected);
+        }
+    }
+}
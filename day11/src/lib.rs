@@ -0,0 +1,213 @@
+use common::{ParseError, Solution};
+use grid::{
+    automaton::{Automaton, Neighbourhood, Settled, State},
+    Cell, Grid, Pos, Topology,
+};
+use render::{Animate, Frame, Layout, Rgb};
+use serde::{Deserialize, Serialize};
+use viewer::{Simulation, Watch};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Place {
+    Floor,
+    Vacant,
+    Occupied,
+}
+impl Cell for Place {
+    const EXPECTED: &'static str = "'L', '#' or '.'";
+
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            'L' => Some(Place::Vacant),
+            '#' => Some(Place::Occupied),
+            '.' => Some(Place::Floor),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Place::Occupied => '#',
+            Place::Vacant => 'L',
+            Place::Floor => '.',
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeatMap {
+    places: Grid<Place>,
+}
+impl SeatMap {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(SeatMap {
+            places: Grid::parse(&common::parse::normalize(input))?,
+        })
+    }
+
+    pub fn places(&self) -> &Grid<Place> {
+        &self.places
+    }
+
+    // floor never changes, and line of sight looks over it
+    pub fn world(&self) -> Grid<State> {
+        self.places.map(|place| match place {
+            Place::Floor => State::Void,
+            Place::Vacant => State::Dead,
+            Place::Occupied => State::Live,
+        })
+    }
+
+    pub fn from_world(world: &Grid<State>) -> Self {
+        let places = world.map(|state| match state {
+            State::Void => Place::Floor,
+            State::Dead => Place::Vacant,
+            State::Live => Place::Occupied,
+        });
+        SeatMap { places }
+    }
+
+    /// Evolves until the seats stop changing, and counts the occupied ones;
+    /// `observe` sees every round. Seating that cycles has no answer.
+    pub fn settle(&self, part: u8, observe: impl FnMut(&Grid<State>)) -> common::Result<usize> {
+        let (neighbourhood, rule) = seating(part)?;
+        let mut automaton = Automaton::new(self.world(), neighbourhood, rule.parse()?);
+        let settled = automaton
+            .settle_observed(SETTLE_LIMIT, observe)
+            .ok_or("seating did not settle")?;
+        log::debug!("{:?} under {}", settled, rule);
+        fixed_point(settled)?;
+        log::trace!("final map\n{}", SeatMap::from_world(automaton.world()));
+        Ok(automaton.count_live())
+    }
+}
+
+impl std::fmt::Display for SeatMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.places)?;
+        writeln!(f, "---")
+    }
+}
+
+// part 1: an empty seat with no occupied neighbours fills up, and an
+// occupied seat with four or more occupied neighbours empties
+//
+// part 2: people look as far as the first seat in each direction, and put
+// up with one more occupied seat
+pub fn seating(part: u8) -> common::Result<(Neighbourhood<Pos>, &'static str)> {
+    match part {
+        1 => Ok((Neighbourhood::adjacent(Topology::Moore), "B0/S0123")),
+        2 => Ok((Neighbourhood::line_of_sight(Topology::Moore), "B0/S01234")),
+        _ => Err(format!("there is no part {}", part).into()),
+    }
+}
+
+// only seats that stop changing answer the puzzle
+fn fixed_point(settled: Settled) -> common::Result<()> {
+    match settled {
+        Settled::FixedPoint { .. } => Ok(()),
+        Settled::Cycle { start, period } => Err(format!(
+            "seating repeats every {} rounds from round {} without settling",
+            period, start
+        )
+        .into()),
+    }
+}
+
+// a generous bound on the rounds of seating before giving up
+pub const SETTLE_LIMIT: usize = 10_000;
+
+pub fn parse_input(input: &str) -> common::Result<SeatMap> {
+    let seat_map = SeatMap::parse(input)?;
+    log::trace!("initial map\n{}", seat_map);
+    Ok(seat_map)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = SeatMap;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> common::Result<SeatMap> {
+        parse_input(input)
+    }
+
+    // part 1
+    fn part1(seat_map: &SeatMap) -> common::Result<usize> {
+        seat_map.settle(1, |_| {})
+    }
+
+    // part 2
+    fn part2(seat_map: &SeatMap) -> common::Result<usize> {
+        seat_map.settle(2, |_| {})
+    }
+}
+
+// each round of seating, with floor pale, empty seats green and occupied
+// seats red
+impl Animate for Day11 {
+    fn frames(seat_map: &SeatMap, _params: &(), part: u8) -> common::Result<Vec<Frame>> {
+        let colour = |state: &State| match state {
+            State::Void => Rgb(0xee, 0xee, 0xe4),
+            State::Dead => Rgb(0x4c, 0xaf, 0x50),
+            State::Live => Rgb(0xd3, 0x2f, 0x2f),
+        };
+        let mut frames = Vec::new();
+        seat_map.settle(part, |world| {
+            frames.push(Frame::new(world, Layout::Square, colour))
+        })?;
+        Ok(frames)
+    }
+}
+
+// a round at a time, until the seats stop changing
+struct Seating {
+    automaton: Automaton<Grid<State>>,
+}
+
+impl Simulation for Seating {
+    fn screen(&self) -> String {
+        format!(
+            "round {}, {} seats occupied\n\n{}",
+            self.automaton.generation(),
+            self.automaton.count_live(),
+            SeatMap::from_world(self.automaton.world()).places
+        )
+    }
+
+    fn step(&mut self) -> bool {
+        let before = self.automaton.world().clone();
+        self.automaton.step();
+        *self.automaton.world() != before
+    }
+}
+
+impl Watch for Day11 {
+    fn simulation(
+        seat_map: &SeatMap,
+        _params: &(),
+        part: u8,
+    ) -> common::Result<Box<dyn Simulation>> {
+        let (neighbourhood, rule) = seating(part)?;
+        let automaton = Automaton::new(seat_map.world(), neighbourhood, rule.parse()?);
+        Ok(Box::new(Seating { automaton }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_a_fixed_point_settles() {
+        assert!(fixed_point(Settled::FixedPoint { generation: 3 }).is_ok());
+        assert!(fixed_point(Settled::Cycle {
+            start: 3,
+            period: 2
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,253 @@
+use common::{parse, ParseError, Solution};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Instruction {
+    N(i32),
+    E(i32),
+    S(i32),
+    W(i32),
+    L(i32),
+    R(i32),
+    F(i32),
+}
+impl FromStr for Instruction {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let split = s.chars().next().map_or(0, char::len_utf8);
+        let (instruction, value) = s.split_at(split);
+        let val: i32 = parse::number(s, value)?;
+        if (instruction == "L" || instruction == "R") && val % 90 != 0 {
+            return Err(ParseError::at(s, value, "a multiple of 90 degrees"));
+        }
+        match instruction {
+            "N" => Ok(Self::N(val)),
+            "E" => Ok(Self::E(val)),
+            "S" => Ok(Self::S(val)),
+            "W" => Ok(Self::W(val)),
+            "L" => Ok(Self::L(val)),
+            "R" => Ok(Self::R(val)),
+            "F" => Ok(Self::F(val)),
+            _ => Err(ParseError::at(s, s, "one of N, E, S, W, L, R or F")),
+        }
+    }
+}
+
+/// East and north.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Coord(pub i32, pub i32);
+
+impl std::ops::Add for Coord {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Coord(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+impl std::ops::Mul<i32> for Coord {
+    type Output = Self;
+    fn mul(self, rhs: i32) -> Self::Output {
+        Coord(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl Coord {
+    fn left_one(&self) -> Self {
+        Coord(-self.1, self.0)
+    }
+    fn right_one(&self) -> Self {
+        Coord(self.1, -self.0)
+    }
+    fn rotate_op<F>(&self, degrees: i32, op: F) -> Self
+    where
+        F: Fn(&Coord) -> Coord,
+    {
+        let num = degrees / 90;
+        let mut c = *self;
+        for _ in 0..num {
+            c = op(&c);
+        }
+        c
+    }
+    pub fn left(&self, degrees: i32) -> Self {
+        self.rotate_op(degrees, Self::left_one)
+    }
+    pub fn right(&self, degrees: i32) -> Self {
+        self.rotate_op(degrees, Self::right_one)
+    }
+}
+
+// for part 1 -- where the ship moves in the direction it faces
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    pub location: Coord,
+    pub direction: Coord,
+}
+
+impl State {
+    pub fn apply_instruction(&self, instruction: &Instruction) -> Self {
+        match *instruction {
+            Instruction::N(v) => State {
+                location: self.location + Coord(0, v),
+                ..*self
+            },
+            Instruction::E(v) => State {
+                location: self.location + Coord(v, 0),
+                ..*self
+            },
+            Instruction::S(v) => State {
+                location: self.location + Coord(0, -v),
+                ..*self
+            },
+            Instruction::W(v) => State {
+                location: self.location + Coord(-v, 0),
+                ..*self
+            },
+            Instruction::L(v) => State {
+                direction: self.direction.left(v),
+                ..*self
+            },
+            Instruction::R(v) => State {
+                direction: self.direction.right(v),
+                ..*self
+            },
+            Instruction::F(v) => State {
+                location: self.location + self.direction * v,
+                ..*self
+            },
+        }
+    }
+}
+
+// for part 2 -- where we move a waypoint (vector) around, and move the ship
+//               in the vector's direction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateWaypoint {
+    pub location: Coord,
+    pub waypoint: Coord,
+}
+impl StateWaypoint {
+    pub fn apply_instruction(&self, instruction: &Instruction) -> Self {
+        match *instruction {
+            Instruction::N(v) => Self {
+                waypoint: self.waypoint + Coord(0, v),
+                ..*self
+            },
+            Instruction::E(v) => Self {
+                waypoint: self.waypoint + Coord(v, 0),
+                ..*self
+            },
+            Instruction::S(v) => Self {
+                waypoint: self.waypoint + Coord(0, -v),
+                ..*self
+            },
+            Instruction::W(v) => Self {
+                waypoint: self.waypoint + Coord(-v, 0),
+                ..*self
+            },
+            Instruction::L(v) => Self {
+                waypoint: self.waypoint.left(v),
+                ..*self
+            },
+            Instruction::R(v) => Self {
+                waypoint: self.waypoint.right(v),
+                ..*self
+            },
+            Instruction::F(v) => Self {
+                location: self.location + self.waypoint * v,
+                ..*self
+            },
+        }
+    }
+}
+
+pub fn parse_input(input: &str) -> common::Result<Vec<Instruction>> {
+    let input = &parse::normalize(input);
+    let instructions = parse::lines(input, str::parse::<Instruction>)?;
+    log::trace!("Instructions: {:?}", &instructions);
+    Ok(instructions)
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Instruction>;
+    type Params = ();
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> common::Result<Vec<Instruction>> {
+        parse_input(input)
+    }
+
+    // turns are only ever by right angles
+    fn validate(instructions: &Vec<Instruction>) -> common::Result<()> {
+        for i in instructions {
+            if let Instruction::L(v) | Instruction::R(v) = i {
+                if v % 90 != 0 {
+                    return Err(format!("{:?} isn't a multiple of 90 degrees", i).into());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // part 1 -- move the ship directly
+    fn part1(instructions: &Vec<Instruction>) -> common::Result<i32> {
+        let initial_state = State {
+            location: Coord(0, 0),
+            direction: Coord(1, 0),
+        };
+        let result = instructions
+            .iter()
+            .fold(initial_state, |state, i| state.apply_instruction(i));
+        log::info!("Result: {:?}", result);
+        Ok(result.location.0.abs() + result.location.1.abs())
+    }
+
+    // part 2 -- move the waypoint, and the ship towards it
+    fn part2(instructions: &Vec<Instruction>) -> common::Result<i32> {
+        let initial_state_waypoint = StateWaypoint {
+            location: Coord(0, 0),
+            waypoint: Coord(10, 1),
+        };
+        let result_waypoint = instructions
+            .iter()
+            .fold(initial_state_waypoint, |state, i| {
+                state.apply_instruction(i)
+            });
+        log::info!("Result: {:?}", result_waypoint);
+        Ok(result_waypoint.location.0.abs() + result_waypoint.location.1.abs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coord_left() {
+        let coord = Coord(2, 1);
+        assert_eq!(Coord(-1, 2), coord.left_one());
+        assert_eq!(Coord(-2, -1), coord.left_one().left_one());
+        assert_eq!(Coord(1, -2), coord.left_one().left_one().left_one());
+        assert_eq!(coord, coord.left_one().left_one().left_one().left_one());
+
+        assert_eq!(Coord(-1, 2), coord.left(90));
+        assert_eq!(Coord(-2, -1), coord.left(180));
+        assert_eq!(Coord(1, -2), coord.left(270));
+    }
+
+    #[test]
+    fn coord_right() {
+        let coord = Coord(2, 1);
+        assert_eq!(Coord(1, -2), coord.right_one());
+        assert_eq!(Coord(-2, -1), coord.right_one().right_one());
+        assert_eq!(Coord(-1, 2), coord.right_one().right_one().right_one());
+        assert_eq!(coord, coord.right_one().right_one().right_one().right_one());
+
+        assert_eq!(Coord(1, -2), coord.right(90));
+        assert_eq!(Coord(-2, -1), coord.right(180));
+        assert_eq!(Coord(-1, 2), coord.right(270));
+    }
+}
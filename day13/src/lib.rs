@@ -0,0 +1,205 @@
+use common::{parse, Annotated, ParseError, Result, Solution};
+use serde::{Deserialize, Serialize};
+
+/// The earliest departure time, and the bus ids in order, `None` for `x`.
+#[derive(Serialize, Deserialize)]
+pub struct Notes {
+    pub earliest: i64,
+    pub ids: Vec<Option<i64>>,
+}
+
+pub fn parse_input(input: &str) -> std::result::Result<Notes, ParseError> {
+    let input = &parse::normalize(input);
+    let mut lines = input.lines();
+
+    let earliest = lines
+        .next()
+        .ok_or_else(|| ParseError::at_end(input, "the earliest departure time"))?;
+    let earliest: i64 = parse::number(input, earliest)?;
+    let ids: std::result::Result<Vec<Option<i64>>, _> = lines
+        .next()
+        .ok_or_else(|| ParseError::at_end(input, "a line of bus ids"))?
+        .split(',')
+        .map(|s| match s {
+            "x" => Ok(None),
+            _ => parse::number(input, s).map(Some),
+        })
+        .collect();
+
+    Ok(Notes {
+        earliest,
+        ids: ids?,
+    })
+}
+
+/// The first bus to leave at or after `earliest`, as (id, departure time).
+/// One leaving at `earliest` itself can be caught without waiting.
+pub fn next_departure(earliest: i64, ids: &[Option<i64>]) -> Option<(i64, i64)> {
+    let mut next: Vec<_> = ids
+        .iter()
+        .filter_map(|ido| {
+            ido.map(|id| {
+                let next = (earliest + id - 1) / id * id;
+                (id, next)
+            })
+        })
+        .collect();
+    next.sort_by_key(|(_, t)| *t);
+    log::debug!("Next busses: {:?}", next);
+    next.first().copied()
+}
+
+/// The earliest time at which each bus departs its offset after the
+/// previous ones, including the buses one at a time from the highest id; the
+/// last start suits them all.
+pub fn schedule_starts(ids: &[Option<i64>]) -> Vec<i64> {
+    // get ids and offsets
+    let mut id_offset: Vec<_> = ids
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, v)| v.map(|v| (v, idx as i64)))
+        .collect();
+    id_offset.sort_by_key(|(id, _offset)| *id);
+    id_offset.reverse();
+
+    // locate the next number in the sequence [start + N*stride] that matches some [M*id + offset].
+    fn find_next_number(start: i64, stride: i64, id: i64, offset: i64) -> i64 {
+        itertools::iterate(start, |v| v + stride)
+            .find(|v| (v + offset) % id == 0)
+            .unwrap()
+    }
+
+    // now iteratively include the busses, starting with the first bus; work out the lowest common multiplier
+    // as the required stride for each next search.
+    id_offset
+        .iter()
+        .scan((0i64, 1i64), |(start, stride), (id, offset)| {
+            let next = find_next_number(*start, *stride, *id, *offset);
+            let next_stride = num::Integer::lcm(stride, id);
+            log::debug!(
+                "id {} offset {} start {} stride {} -> next {} next_stride {}",
+                id,
+                offset,
+                start,
+                stride,
+                next,
+                next_stride
+            );
+            *start = next;
+            *stride = next_stride;
+            Some(next)
+        })
+        .collect()
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Notes;
+    type Params = ();
+    type Part1 = Annotated<i64>;
+    type Part2 = Annotated<i64>;
+
+    fn parse(input: &str) -> Result<Notes> {
+        Ok(parse_input(input)?)
+    }
+
+    // Part 1
+    fn part1(notes: &Notes) -> Result<Annotated<i64>> {
+        log::debug!("Earliest: {}, IDs: {:?}", notes.earliest, notes.ids);
+        let (next_id, next_time) = next_departure(notes.earliest, &notes.ids).ok_or("no bus")?;
+        let wait = next_time - notes.earliest;
+        log::info!("Next bus is {}, time is {}", next_id, next_time);
+        Ok(Annotated::new(next_id * wait)
+            .with("bus", next_id)
+            .with("wait", wait))
+    }
+
+    // Part 2 with LCM -- brute linear search proved infeasible and I needed to take a more iterative
+    // approach. Divide and conquer. As per notes.txt.
+    fn part2(notes: &Notes) -> Result<Annotated<i64>> {
+        let starts = schedule_starts(&notes.ids);
+        log::info!("Starts: {:?}", starts);
+        let earliest = starts.last().ok_or("no busses")?;
+        Ok(Annotated::new(*earliest).with("starts", starts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const PRIMES: [i64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+    #[test]
+    fn bus_leaving_at_earliest_needs_no_wait() {
+        let ids = [Some(7), None, Some(13)];
+        assert_eq!(Some((13, 39)), next_departure(39, &ids));
+        assert_eq!(Some((7, 42)), next_departure(40, &ids));
+    }
+
+    // tries every time from `earliest` in turn
+    fn next_departure_by_scan(earliest: i64, ids: &[Option<i64>]) -> Option<(i64, i64)> {
+        if ids.iter().all(Option::is_none) {
+            return None;
+        }
+        (earliest..).find_map(|t| {
+            ids.iter()
+                .flatten()
+                .find(|&&id| t % id == 0)
+                .map(|&id| (id, t))
+        })
+    }
+
+    // tries every time from 0 until the buses all leave at their offsets
+    fn schedule_start_by_scan(buses: &[(i64, i64)]) -> i64 {
+        (0..)
+            .find(|t| buses.iter().all(|(id, offset)| (t + offset) % id == 0))
+            .unwrap()
+    }
+
+    // distinct primes, as the real bus ids are, with a few `x` between them
+    fn bus_ids() -> impl Strategy<Value = Vec<Option<i64>>> {
+        prop::sample::subsequence(PRIMES.to_vec(), 1..=5)
+            .prop_shuffle()
+            .prop_flat_map(|ids| {
+                let n = ids.len();
+                (Just(ids), prop::collection::vec(0..3usize, n))
+            })
+            .prop_map(|(ids, gaps)| {
+                ids.iter()
+                    .zip(gaps)
+                    .flat_map(|(&id, gap)| {
+                        std::iter::repeat_n(None, gap).chain(std::iter::once(Some(id)))
+                    })
+                    .collect()
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn next_departure_matches_scan(earliest in 0..10_000i64, ids in bus_ids()) {
+            prop_assert_eq!(
+                next_departure(earliest, &ids),
+                next_departure_by_scan(earliest, &ids)
+            );
+        }
+
+        #[test]
+        fn schedule_starts_match_scan(ids in bus_ids()) {
+            let mut buses: Vec<(i64, i64)> = ids
+                .iter()
+                .enumerate()
+                .filter_map(|(offset, id)| id.map(|id| (id, offset as i64)))
+                .collect();
+            // included highest id first
+            buses.sort_by_key(|&(id, _)| -id);
+
+            let expected: Vec<i64> = (1..=buses.len())
+                .map(|n| schedule_start_by_scan(&buses[..n]))
+                .collect();
+            prop_assert_eq!(schedule_starts(&ids), expected);
+        }
+    }
+}
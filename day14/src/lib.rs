@@ -0,0 +1,422 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use anyhow::{anyhow, Result};
+use common::{parse, ParseError, Solution};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+lazy_static::lazy_static! {
+    static ref RE_MASK: Regex = Regex::new(r"^mask = (.*)$").unwrap();
+}
+
+// the 36 bits of a mask line, most significant first
+fn mask_bits(s: &str) -> Result<&str, ParseError> {
+    let captures = RE_MASK
+        .captures(s)
+        .ok_or_else(|| ParseError::at(s, s, "\"mask = \" and 36 bits"))?;
+    let bits = captures.get(1).unwrap().as_str();
+    if bits.chars().count() != 36 {
+        return Err(ParseError::at(s, bits, "36 bits of '0', '1' or 'X'"));
+    }
+    Ok(bits)
+}
+
+// mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Mask {
+    pub set: u64,
+    pub reset: u64,
+}
+impl Mask {
+    /// Sets the 1 bits and clears the 0 bits of `value`.
+    pub fn apply(&self, value: u64) -> u64 {
+        value & !self.reset | self.set
+    }
+}
+impl FromStr for Mask {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bits = mask_bits(s)?;
+        let mut set = 0u64;
+        let mut reset = 0u64;
+        for (i, (pos, c)) in bits.char_indices().enumerate() {
+            let loc = 35_u64 - i as u64;
+            match c {
+                'X' => {}
+                '1' => {
+                    set |= 1u64 << loc;
+                }
+                '0' => {
+                    reset |= 1u64 << loc;
+                }
+                _ => return Err(ParseError::at(s, &bits[pos..], "'0', '1' or 'X'")),
+            };
+        }
+        Ok(Mask { set, reset })
+    }
+}
+impl Display for Mask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "mask set: {:b}, reset: {:b}", self.set, self.reset)
+    }
+}
+
+// mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+// for part 2,
+// 0 -> unchanged
+// 1 -> set to 1
+// X -> floating, permitted to take all values
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaskAddress {
+    set_mask: u64,
+    floating_bits: Vec<u8>,
+}
+
+impl MaskAddress {
+    pub fn set_mask(&self) -> u64 {
+        self.set_mask
+    }
+
+    /// Positions of the floating bits, smallest first.
+    pub fn floating_bits(&self) -> &[u8] {
+        &self.floating_bits
+    }
+
+    /// Every address `address` decodes to, one for each combination of the
+    /// floating bits.
+    pub fn addresses_iter(&self, address: u64) -> MaskAddressIterator<'_> {
+        MaskAddressIterator {
+            floating_bit_values: 0u64,
+            floating_bit_last: (1u64 << self.floating_bits.len()) - 1,
+            completed: false,
+            mask_address: self,
+            address,
+        }
+    }
+}
+impl FromStr for MaskAddress {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bits = mask_bits(s)?;
+        let mut set_mask = 0u64;
+        let mut floating_bits = Vec::new();
+        for (i, (pos, c)) in bits.char_indices().enumerate() {
+            let loc = 35_u64 - i as u64;
+            match c {
+                'X' => floating_bits.push(loc as u8),
+                '1' => set_mask |= 1u64 << loc,
+                '0' => (),
+                _ => return Err(ParseError::at(s, &bits[pos..], "'0', '1' or 'X'")),
+            };
+        }
+        // ensure floating bit addresses are sorted smallest to largest
+        floating_bits.sort();
+        Ok(MaskAddress {
+            set_mask,
+            floating_bits,
+        })
+    }
+}
+
+pub struct MaskAddressIterator<'a> {
+    floating_bit_values: u64,
+    floating_bit_last: u64,
+    completed: bool,
+    mask_address: &'a MaskAddress,
+    address: u64,
+}
+impl<'a> Iterator for MaskAddressIterator<'a> {
+    type Item = u64;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.completed {
+            return None;
+        }
+
+        // take address, apply set mask
+        let mut addr = self.address;
+        addr |= self.mask_address.set_mask;
+
+        // now extract bits from floating_bit_values and place them in the address
+        let mut shift_extract = self.floating_bit_values;
+        for loc in self.mask_address.floating_bits.iter() {
+            // reset bit in address, then extract and apply our bit
+            addr &= !(1u64 << loc);
+            addr |= (shift_extract & 1) << loc;
+            // shift right, ditching last bit
+            shift_extract >>= 1;
+        }
+
+        // increment out counter
+        if self.floating_bit_values == self.floating_bit_last {
+            self.completed = true;
+        } else {
+            self.floating_bit_values += 1;
+        }
+
+        Some(addr)
+    }
+}
+
+// mem[8] = 11
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct Instruction {
+    pub addr: u64,
+    pub value: u64,
+}
+lazy_static::lazy_static! {
+    static ref RE_INSTRUCTION: Regex = Regex::new(r"^mem\[(\d+)\] = (\d+)$").unwrap();
+}
+impl FromStr for Instruction {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let captures = RE_INSTRUCTION
+            .captures(s)
+            .ok_or_else(|| ParseError::at(s, s, "a write like \"mem[8] = 11\""))?;
+        Ok(Instruction {
+            addr: parse::number(s, captures.get(1).unwrap().as_str())?,
+            value: parse::number(s, captures.get(2).unwrap().as_str())?,
+        })
+    }
+}
+
+// a line of the program is either a mask (interpreted both ways, as part 1
+// and part 2 read it differently) or a memory write
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Line {
+    Mask(Mask, MaskAddress),
+    Mem(Instruction),
+}
+impl FromStr for Line {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with("mask") {
+            Ok(Line::Mask(s.parse()?, s.parse()?))
+        } else if s.starts_with("mem") {
+            Ok(Line::Mem(s.parse()?))
+        } else {
+            Err(ParseError::at(s, s, "\"mask\" or \"mem\""))
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Program {
+    pub lines: Vec<Line>,
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Program;
+    type Params = ();
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> common::Result<Program> {
+        let input = &parse::normalize(input);
+        let lines = parse::lines(input, str::parse::<Line>)?;
+        Ok(Program { lines })
+    }
+
+    // each mask line's two readings are of the same 36 bits
+    fn validate(program: &Program) -> common::Result<()> {
+        let bits = (1u64 << 36) - 1;
+        for l in program.lines.iter() {
+            if let Line::Mask(mask, address) = l {
+                let floating: Vec<u8> = (0..36)
+                    .filter(|b| (mask.set | mask.reset) & (1 << b) == 0)
+                    .collect();
+                if mask.set & mask.reset != 0
+                    || (mask.set | mask.reset) & !bits != 0
+                    || address.set_mask != mask.set
+                    || address.floating_bits != floating
+                {
+                    return Err(format!("inconsistent mask: {:?}", l).into());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // part 1
+    fn part1(program: &Program) -> common::Result<u64> {
+        let mut mask: Mask = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX"
+            .parse()
+            .unwrap();
+        let mut memory: HashMap<u64, u64> = HashMap::new();
+        for l in program.lines.iter() {
+            match l {
+                Line::Mask(m, _) => {
+                    mask = *m;
+                    //println!("Mask: {}", &mask);
+                }
+                Line::Mem(instruction) => {
+                    let value_masked = mask.apply(instruction.value);
+                    memory.insert(instruction.addr, value_masked);
+                    //println!("Instruction: {:?}", instruction);
+                    //println!("Memory {:?}", memory);
+                }
+            }
+        }
+        let memory_sum: u64 = memory.values().sum();
+        Ok(memory_sum)
+    }
+
+    // part 2
+    fn part2(program: &Program) -> common::Result<u64> {
+        let mut memory: HashMap<u64, u64> = HashMap::new();
+        let mut mask: Option<&MaskAddress> = None;
+        for l in program.lines.iter() {
+            match l {
+                Line::Mask(_, m) => mask = Some(m),
+                Line::Mem(instruction) => {
+                    let addr_mask = mask.ok_or(anyhow!("mask not set yet"))?;
+                    for addr in addr_mask.addresses_iter(instruction.addr) {
+                        memory.insert(addr, instruction.value);
+                    }
+                }
+            }
+        }
+        log::info!("Set {} total memory addresses", memory.len());
+        let memory_sum: u64 = memory.values().sum();
+        Ok(memory_sum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use proptest::prelude::*;
+
+    // the address bit by bit, most significant first, with every choice for
+    // each floating bit
+    fn expand_naively(mask: &str, address: u64) -> Vec<u64> {
+        let mut addresses = vec![0u64];
+        for (i, c) in mask.chars().enumerate() {
+            let bit = (address >> (35 - i)) & 1;
+            let choices: &[u64] = match c {
+                '0' => &[bit],
+                '1' => &[1],
+                _ => &[0, 1],
+            };
+            addresses = addresses
+                .iter()
+                .flat_map(|a| choices.iter().map(move |b| a << 1 | b))
+                .collect();
+        }
+        let high_bits = address >> 36 << 36;
+        addresses.iter().map(|a| a | high_bits).collect()
+    }
+
+    // the value bit by bit, most significant first
+    fn apply_naively(mask: &str, value: u64) -> u64 {
+        mask.chars().enumerate().fold(value, |v, (i, c)| {
+            let loc = 35 - i;
+            match c {
+                '0' => v & !(1 << loc),
+                '1' => v | 1 << loc,
+                _ => v,
+            }
+        })
+    }
+
+    // 36 mask bits, with at most `max_floating` of them floating
+    fn mask_bits(max_floating: usize) -> impl Strategy<Value = String> {
+        prop::collection::vec(
+            prop_oneof![3 => Just('0'), 2 => Just('1'), 1 => Just('X')],
+            36,
+        )
+        .prop_map(move |bits| {
+            let mut floating = 0;
+            bits.iter()
+                .map(|&c| match c {
+                    'X' if floating == max_floating => '0',
+                    'X' => {
+                        floating += 1;
+                        'X'
+                    }
+                    c => c,
+                })
+                .collect()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn masked_addresses_match_naive_expansion(bits in mask_bits(10), address in 0..1u64 << 36) {
+            let mask: MaskAddress = format!("mask = {}", bits).parse().unwrap();
+            let mut addresses: Vec<u64> = mask.addresses_iter(address).collect();
+            addresses.sort_unstable();
+            let mut expected = expand_naively(&bits, address);
+            expected.sort_unstable();
+            prop_assert_eq!(addresses, expected);
+        }
+
+        #[test]
+        fn masked_values_match_naive_application(bits in mask_bits(36), value in 0..1u64 << 36) {
+            let mask: Mask = format!("mask = {}", bits).parse().unwrap();
+            prop_assert_eq!(mask.apply(value), apply_naively(&bits, value));
+        }
+    }
+
+    #[test]
+    fn mask_parse() {
+        let mask: Mask = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X"
+            .parse()
+            .unwrap();
+        assert_eq!(0b01000000, mask.set);
+        assert_eq!(0b00000010, mask.reset);
+    }
+
+    #[test]
+    fn mask_apply() {
+        let mask: Mask = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X"
+            .parse()
+            .unwrap();
+        assert_eq!(73, mask.apply(11));
+        assert_eq!(101, mask.apply(101));
+        assert_eq!(64, mask.apply(0));
+    }
+
+    #[test]
+    fn bad_mask_bit_located() {
+        let input = "mem[8] = 11\nmask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX2X\n";
+        let err = Day14::parse(input).err().unwrap().to_string();
+        assert_eq!(
+            "line 2, column 42: expected '0', '1' or 'X', found \"2X\"",
+            err
+        );
+    }
+
+    #[test]
+    fn instruction_parse() {
+        let inst: Instruction = "mem[8] = 11".parse().unwrap();
+        assert_eq!(8, inst.addr);
+        assert_eq!(11, inst.value);
+    }
+
+    #[test]
+    fn mask_address_iterate() {
+        //let mask: MaskAddress = "mask = 000000000000000000000000000XX0010XXX".parse()?;
+        let mask: MaskAddress = "mask = 000000000000000000000000000000000XXX"
+            .parse()
+            .unwrap();
+        // all floating, should replace all of these with iterated values
+        itertools::assert_equal(0u64..=0b111, mask.addresses_iter(0b000));
+        itertools::assert_equal(0u64..=0b111, mask.addresses_iter(0b101));
+        itertools::assert_equal(0u64..=0b111, mask.addresses_iter(0b111));
+    }
+
+    #[test]
+    fn mask_address_set_and_iterate() {
+        let mask: MaskAddress = "mask = 00000000000000000000000000010000000X"
+            .parse()
+            .unwrap();
+        // all floating, should replace all of these with iterated values
+        let addr = 0xF000;
+        itertools::assert_equal(
+            [addr + 256, addr + 256 + 1].iter().copied(),
+            mask.addresses_iter(addr),
+        );
+    }
+}
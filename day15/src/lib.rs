@@ -0,0 +1,145 @@
+use common::{parse, Result, Solution};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The puzzle constants, as in the puzzle unless changed. The starting
+/// numbers are the input itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    /// The round whose number part 1 asks for.
+    pub part1_rounds: i32,
+    /// The round whose number part 2 asks for.
+    pub part2_rounds: i32,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            part1_rounds: 2020,
+            part2_rounds: 30000000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct NumberSpoken {
+    round: i32,
+    prior_round: Option<i32>,
+}
+
+/// The numbers spoken in each of the first `round` rounds.
+pub fn run_game(round: i32, starting_numbers: &[i32]) -> impl Iterator<Item = i32> {
+    struct State {
+        memory: HashMap<i32, NumberSpoken>,
+        last_number_spoken: i32,
+    }
+
+    let starting_numbers = starting_numbers.to_vec();
+    let rounds = 1..=round;
+    rounds.scan(
+        State {
+            memory: HashMap::new(),
+            last_number_spoken: 0,
+        },
+        move |state, round| {
+            let num = {
+                if round <= starting_numbers.len() as i32 {
+                    starting_numbers[round as usize - 1]
+                } else if let Some(m) = state.memory.get(&state.last_number_spoken) {
+                    if let Some(prior) = m.prior_round {
+                        m.round - prior
+                    } else {
+                        0
+                    }
+                } else {
+                    0
+                }
+            };
+            // update memory
+            if let Some(m) = state.memory.get_mut(&num) {
+                *m = NumberSpoken {
+                    round,
+                    prior_round: Some(m.round),
+                };
+            } else {
+                state.memory.insert(
+                    num,
+                    NumberSpoken {
+                        round,
+                        prior_round: None,
+                    },
+                );
+            }
+            // record last number spoken
+            state.last_number_spoken = num;
+            Some(num)
+        },
+    )
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<i32>> {
+    let input = &parse::normalize(input);
+    let numbers: std::result::Result<Vec<i32>, _> = input
+        .trim()
+        .split(',')
+        .map(|s| parse::number(input, s))
+        .collect();
+    Ok(numbers?)
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Vec<i32>;
+    type Params = Params;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<i32>> {
+        parse_input(input)
+    }
+
+    fn validate(starting_numbers: &Vec<i32>) -> Result<()> {
+        if starting_numbers.is_empty() {
+            return Err("no starting numbers".into());
+        }
+        Ok(())
+    }
+
+    fn part1(starting_numbers: &Vec<i32>) -> Result<i32> {
+        Self::part1_with(starting_numbers, &Params::default())
+    }
+
+    fn part2(starting_numbers: &Vec<i32>) -> Result<i32> {
+        Self::part2_with(starting_numbers, &Params::default())
+    }
+
+    // part 1
+    fn part1_with(starting_numbers: &Vec<i32>, params: &Params) -> Result<i32> {
+        let last = run_game(params.part1_rounds, starting_numbers)
+            .last()
+            .ok_or("no numbers spoken")?;
+        Ok(last)
+    }
+
+    // part 2
+    fn part2_with(starting_numbers: &Vec<i32>, params: &Params) -> Result<i32> {
+        let last = run_game(params.part2_rounds, starting_numbers)
+            .last()
+            .ok_or("no numbers spoken")?;
+        Ok(last)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_correct() {
+        let numbers: Vec<_> = run_game(10, &[0, 3, 6]).collect();
+        assert_eq!(vec![0, 3, 6, 0, 3, 3, 1, 0, 4, 0], numbers);
+        assert_eq!(Some(436), run_game(2020, &[0, 3, 6]).last());
+    }
+}
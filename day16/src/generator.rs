@@ -0,0 +1,196 @@
+// Ticket notes in which the fields can be placed by elimination.
+//
+// Every field accepts a common band of values, so most of each column fits
+// any field, and a second range whose top rises with the field's rank. The
+// column of each field holds a value just above the top of the previous
+// rank's range, so it fits that field and those ranked after it but none
+// ranked before: one column fits only the last field, the next only the
+// last two, and so on.
+
+use std::ops::RangeInclusive;
+
+use generate::{Generate, Generated, Rng};
+
+use crate::Day16;
+
+const NAMES: [&str; 20] = [
+    "departure location",
+    "departure station",
+    "departure platform",
+    "departure track",
+    "departure date",
+    "departure time",
+    "arrival location",
+    "arrival station",
+    "arrival platform",
+    "arrival track",
+    "class",
+    "duration",
+    "price",
+    "route",
+    "row",
+    "seat",
+    "train",
+    "type",
+    "wagon",
+    "zone",
+];
+
+// every field accepts these
+const COMMON: (i32, i32) = (50, 300);
+
+// the top of the second range of the field of `rank`
+fn second_top(rank: usize) -> i32 {
+    500 + 20 * rank as i32
+}
+
+// a value only fields of `rank` and later accept
+fn witness(rng: &mut Rng, rank: usize) -> i32 {
+    rng.i32(second_top(rank) - 19..=second_top(rank))
+}
+
+// a value no field accepts: between the ranges, or above all of them
+fn invalid_value(rng: &mut Rng) -> i32 {
+    match rng.bool() {
+        true => rng.i32(341..360),
+        false => rng.i32(900..1000),
+    }
+}
+
+fn ticket(values: &[i32]) -> String {
+    let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+    values.join(",")
+}
+
+impl Generate for Day16 {
+    const SIZE_OF: &'static str = "fields";
+    const DEFAULT_SIZE: usize = NAMES.len();
+    const SIZES: RangeInclusive<usize> = 1..=NAMES.len();
+
+    fn valid(rng: &mut Rng, size: usize) -> Generated {
+        // fields by rank, and the column of each
+        let mut fields: Vec<&str> = NAMES.to_vec();
+        rng.shuffle(&mut fields);
+        fields.truncate(size);
+        let mut columns: Vec<usize> = (0..size).collect();
+        rng.shuffle(&mut columns);
+
+        let mut specs: Vec<String> = fields
+            .iter()
+            .enumerate()
+            .map(|(rank, name)| {
+                format!(
+                    "{}: {}-{} or {}-{}",
+                    name,
+                    rng.i32(1..=COMMON.0),
+                    rng.i32(COMMON.1..=340),
+                    rng.i32(360..=400),
+                    second_top(rank)
+                )
+            })
+            .collect();
+        rng.shuffle(&mut specs);
+
+        let common = |rng: &mut Rng| rng.i32(COMMON.0..=COMMON.1);
+        let mine: Vec<i32> = (0..size).map(|_| common(rng)).collect();
+
+        // each column gets its field's witness on at least one ticket
+        let mut nearby = Vec::new();
+        let mut error_rate = 0;
+        for t in 0..size * 3 + 10 {
+            let mut values = vec![0; size];
+            for (rank, &column) in columns.iter().enumerate() {
+                values[column] = match rank == t || rng.usize(..4) == 0 {
+                    true => witness(rng, rank),
+                    false => common(rng),
+                };
+            }
+            if t >= size && rng.usize(..4) == 0 {
+                let invalid = invalid_value(rng);
+                values[rng.usize(..size)] = invalid;
+                error_rate += invalid;
+            }
+            nearby.push(ticket(&values));
+        }
+        rng.shuffle(&mut nearby);
+
+        let departure: i64 = fields
+            .iter()
+            .zip(&columns)
+            .filter(|(name, _)| name.starts_with("departure"))
+            .map(|(_, &column)| mine[column] as i64)
+            .product();
+
+        let input = format!(
+            "{}\n\nyour ticket:\n{}\n\nnearby tickets:\n{}\n",
+            specs.join("\n"),
+            ticket(&mine),
+            nearby.join("\n")
+        );
+        Generated::new(input)
+            .with_part1(error_rate)
+            .with_part2(departure)
+    }
+
+    fn corrupt(rng: &mut Rng, line: &str) -> String {
+        if line.ends_with(':') {
+            line.replacen("ticket", "tickt", 1)
+        } else if line.contains(" or ") {
+            line.replacen(" or ", " and ", 1)
+        } else {
+            generate::insert_at_random(rng, line, "x")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+    use generate::{generate, Validity};
+
+    #[test]
+    fn generated_notes_have_the_expected_answers() {
+        for seed in 0..10 {
+            for size in [1, 6, 20] {
+                let generated = generate::<Day16>(seed, size, Validity::Valid).unwrap();
+                let problem = Day16::parse(&generated.input).unwrap();
+                assert_eq!(problem.field_specs.len(), size);
+                assert_eq!(
+                    Some(Day16::part1(&problem).unwrap().to_string()),
+                    generated.part1
+                );
+                assert_eq!(
+                    Some(Day16::part2(&problem).unwrap().to_string()),
+                    generated.part2
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sizes_out_of_range_are_refused() {
+        for size in [0, 21] {
+            assert!(generate::<Day16>(0, size, Validity::Valid).is_err());
+        }
+    }
+
+    #[test]
+    fn corrupted_notes_fail_to_parse() {
+        for seed in 0..20 {
+            let generated = generate::<Day16>(seed, 10, Validity::Invalid).unwrap();
+            assert!(
+                Day16::parse(&generated.input).is_err(),
+                "{}",
+                generated.input
+            );
+        }
+    }
+
+    #[test]
+    fn seeds_are_reproducible() {
+        let input = |seed| generate::<Day16>(seed, 10, Validity::Valid).unwrap().input;
+        assert_eq!(input(1), input(1));
+        assert_ne!(input(1), input(2));
+    }
+}
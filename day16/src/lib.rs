@@ -0,0 +1,292 @@
+use anyhow::Result;
+use common::{parse, ParseError, Solution};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, ops::RangeInclusive, str::FromStr};
+
+mod generator;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldRange(pub Vec<RangeInclusive<i32>>);
+impl FieldRange {
+    pub fn create(ranges: Vec<RangeInclusive<i32>>) -> Self {
+        FieldRange(ranges)
+    }
+    pub fn contains(&self, v: &i32) -> bool {
+        self.0.iter().any(|r| r.contains(v))
+    }
+}
+
+// class: 1-3 or 5-7
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldSpec {
+    pub name: String,
+    pub ranges: FieldRange,
+}
+impl FromStr for FieldSpec {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref RE_FIELD: Regex = Regex::new(r"^([a-z ]+): (.*)$").unwrap();
+            static ref RE_RANGE: Regex = Regex::new(r"^(\d+)-(\d+)$").unwrap();
+        }
+
+        let outer = RE_FIELD
+            .captures(s)
+            .ok_or_else(|| ParseError::at(s, s, "a field like \"class: 1-3 or 5-7\""))?;
+        let name = outer[1].to_string();
+
+        let mut ranges = Vec::new();
+        for range_str in outer.get(2).unwrap().as_str().split(" or ") {
+            let captures = RE_RANGE
+                .captures(range_str)
+                .ok_or_else(|| ParseError::at(s, range_str, "a range like \"1-3\""))?;
+            let i0: i32 = parse::number(s, captures.get(1).unwrap().as_str())?;
+            let i1: i32 = parse::number(s, captures.get(2).unwrap().as_str())?;
+            ranges.push(i0..=i1);
+        }
+
+        Ok(FieldSpec {
+            name,
+            ranges: FieldRange::create(ranges),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ticket(pub Vec<i32>);
+impl FromStr for Ticket {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values: Result<Vec<i32>, _> = s.split(',').map(|v| parse::number(s, v)).collect();
+        Ok(Ticket(values?))
+    }
+}
+
+// the next line of `s` must be exactly `expected`
+fn expect_line(s: &str, line: Option<&str>, expected: &str) -> Result<(), ParseError> {
+    let description = match expected {
+        "" => "a blank line".to_string(),
+        _ => format!("\"{}\"", expected),
+    };
+    match line {
+        Some(l) if l == expected => Ok(()),
+        Some(l) => Err(ParseError::at(s, l, description)),
+        None => Err(ParseError::at_end(s, description)),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Problem {
+    pub field_specs: Vec<FieldSpec>,
+    pub ticket: Ticket,
+    pub nearby_tickets: Vec<Ticket>,
+}
+
+impl Problem {
+    /// Values on `ticket` that fit none of the fields.
+    pub fn ticket_invalid_fields<'a>(
+        &'a self,
+        ticket: &'a Ticket,
+    ) -> impl Iterator<Item = &'a i32> + 'a {
+        let field_specs = &self.field_specs;
+        ticket
+            .0
+            .iter()
+            .filter(move |f| !field_specs.iter().any(|fs| fs.ranges.contains(f)))
+    }
+
+    /// The position on the tickets of each field, in the order of
+    /// `field_specs`, worked out from the valid nearby tickets.
+    pub fn field_positions(&self) -> Vec<usize> {
+        let valid_nearby: Vec<Ticket> = self
+            .nearby_tickets
+            .iter()
+            .filter(|&t| self.ticket_invalid_fields(t).count() == 0)
+            .cloned()
+            .collect();
+        log::trace!("Valid nearby tickets: {:?}", valid_nearby);
+
+        // find sets of fields that could match
+        let all_field_numbers: Vec<_> = self
+            .field_specs
+            .iter()
+            .map(|f| find_field_numbers_possible(f, &valid_nearby))
+            .collect();
+
+        log::debug!("All field numbers: {:?}", all_field_numbers);
+
+        // now reduce each to a unique field -- one where we have only a single possible option
+        // loop through all the field ranges
+        //  - find a number that exists in only one set -> this is the only option for this set
+        //  - remove the other numbers from this set
+        //  - repeat
+        //  - stop when all sets contain only a single entry
+        let mut finished = false;
+        let mut sets = all_field_numbers.clone();
+        while !finished {
+            for i in 0..sets.len() {
+                // find a number in this set that is unique
+                let unique_num_extract = {
+                    let set = &sets[i];
+                    set.iter()
+                        .find(|&v| {
+                            // skip self (checking the _reference_, not values)
+                            // no other sets contain this value
+                            skip_nth_value(sets.iter(), i).all(|s| !s.contains(v))
+                        })
+                        .copied() // needed to break reference (borrow checker)
+                };
+                // clear this set, and replace with only the unique number
+                if let Some(num) = unique_num_extract {
+                    let mut_set = &mut sets[i];
+                    if mut_set.len() == 1 {
+                        continue;
+                    }
+                    log::debug!("Reducing set #{} {:?} -> {}", i, mut_set, num);
+                    mut_set.clear();
+                    mut_set.insert(num);
+                    continue; // start next loop
+                }
+            }
+            // check if all sets are single elements
+            if sets.iter().all(|s| s.len() == 1) {
+                finished = true;
+            }
+        }
+
+        log::debug!("Unique sets: {:?}", sets);
+        sets.iter().map(|set| *set.iter().next().unwrap()).collect()
+    }
+}
+
+impl FromStr for Problem {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = &parse::normalize(s);
+        let mut lines = s.lines();
+
+        let mut fields: Vec<FieldSpec> = Vec::new();
+        for l in lines.by_ref() {
+            if l.is_empty() {
+                break;
+            }
+            fields.push(l.parse().map_err(|e: ParseError| e.within(s, l))?);
+        }
+
+        // already ate the empty line above, just eat the title
+        expect_line(s, lines.next(), "your ticket:")?;
+        let l = lines
+            .next()
+            .ok_or_else(|| ParseError::at_end(s, "your ticket"))?;
+        let ticket: Ticket = l.parse().map_err(|e: ParseError| e.within(s, l))?;
+
+        // eat the empty line and title
+        expect_line(s, lines.next(), "")?;
+        expect_line(s, lines.next(), "nearby tickets:")?;
+        let mut nearby_tickets: Vec<Ticket> = Vec::new();
+        for l in lines {
+            nearby_tickets.push(l.parse().map_err(|e: ParseError| e.within(s, l))?);
+        }
+
+        Ok(Problem {
+            field_specs: fields,
+            ticket,
+            nearby_tickets,
+        })
+    }
+}
+
+pub fn find_field_numbers_possible(
+    field_spec: &FieldSpec,
+    valid_tickets: &[Ticket],
+) -> HashSet<usize> {
+    let mut matches = HashSet::new();
+    let num_fields = valid_tickets.first().map(|t| t.0.len()).unwrap_or(0);
+    for i in 0..num_fields {
+        if valid_tickets.iter().all(|t| {
+            let field_val = t.0[i];
+            field_spec.ranges.contains(&field_val)
+        }) {
+            matches.insert(i);
+        }
+    }
+    matches
+}
+
+fn skip_nth_value<T>(source: impl Iterator<Item = T>, n: usize) -> impl Iterator<Item = T> {
+    source
+        .enumerate()
+        .filter(move |(i, _v)| *i != n)
+        .map(|(_i, v)| v)
+}
+
+pub fn parse_input(input: &str) -> Result<Problem, ParseError> {
+    let problem: Problem = input.parse()?;
+    log::trace!("Problem: {:?}", problem);
+    Ok(problem)
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Problem;
+    type Params = ();
+    type Part1 = i32;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> common::Result<Problem> {
+        Ok(parse_input(input)?)
+    }
+
+    //
+    // Part 1
+    //
+    fn part1(problem: &Problem) -> common::Result<i32> {
+        for t in problem.nearby_tickets.iter() {
+            let invalid_fields: Vec<_> = problem.ticket_invalid_fields(t).collect();
+            log::trace!("invalid fields: {:?}", invalid_fields);
+        }
+        let ticket_scanning_error_rate: i32 = problem
+            .nearby_tickets
+            .iter()
+            .flat_map(|t| problem.ticket_invalid_fields(t))
+            .sum();
+        Ok(ticket_scanning_error_rate)
+    }
+
+    //
+    // Part 2
+    //
+    fn part2(problem: &Problem) -> common::Result<i64> {
+        let my_field_values: Vec<_> = problem
+            .field_positions()
+            .iter()
+            .map(|&nn| problem.ticket.0[nn])
+            .collect();
+
+        let departure_field_values: Vec<_> = problem
+            .field_specs
+            .iter()
+            .zip(my_field_values.iter())
+            .filter_map(|(fs, val)| {
+                if fs.name.contains("departure") {
+                    Some(val)
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        log::info!("My field values: {:?}", &my_field_values);
+        log::info!("Departure field values: {:?}", &departure_field_values);
+
+        let product: i64 = departure_field_values.iter().map(|&v| *v as i64).product();
+
+        Ok(product)
+    }
+}
@@ -0,0 +1,281 @@
+use anyhow::Result;
+use common::{ParseError, Solution};
+use grid::{
+    automaton::{Automaton, Neighbourhood, Point, Rule, Sparse},
+    Grid,
+};
+use itertools::Itertools;
+use render::{Animate, Frame, Layout, Rgb};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, str::FromStr};
+use viewer::{Simulation, Watch};
+
+// the active cubes of the initial slice, as (x, y)
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Slice(pub Vec<(i64, i64)>);
+
+impl FromStr for Slice {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = &common::parse::normalize(s);
+        let mut active = Vec::new();
+        let lines: Vec<_> = s.lines().collect();
+        let first = lines
+            .first()
+            .ok_or_else(|| ParseError::at_end(s, "a row of cubes"))?;
+        let x_offset = first.len() / 2 + 1;
+        let y_offset = lines.len() / 2 + 1;
+        for (y, line) in lines.iter().enumerate() {
+            for (x, (i, ch)) in line.char_indices().enumerate() {
+                match ch {
+                    '#' => {
+                        let coord = (x as i64 - x_offset as i64, y as i64 - y_offset as i64);
+                        active.push(coord);
+                    }
+                    '.' => {}
+                    _ => return Err(ParseError::at(s, &line[i..], "'.' or '#'")),
+                }
+            }
+        }
+        Ok(Slice(active))
+    }
+}
+
+impl Slice {
+    // embed in N dimensions, with the extra coordinates all zero
+    pub fn embed<const N: usize>(&self) -> Sparse<Point<N>> {
+        Sparse::new(self.0.iter().map(|&(x, y)| {
+            let mut p = Point([0; N]);
+            p.0[0] = x;
+            p.0[1] = y;
+            p
+        }))
+    }
+}
+
+// one x-y slice for each combination of the other coordinates in use
+pub fn render<const N: usize>(grid: &Sparse<Point<N>>) -> String {
+    let mut slices: BTreeMap<&[i64], Vec<(i64, i64)>> = BTreeMap::new();
+    for p in grid.live() {
+        slices.entry(&p.0[2..]).or_default().push((p.0[0], p.0[1]));
+    }
+    let (min_x, max_x) = grid
+        .live()
+        .map(|p| p.0[0])
+        .minmax()
+        .into_option()
+        .unwrap_or((0, 0));
+    let (min_y, max_y) = grid
+        .live()
+        .map(|p| p.0[1])
+        .minmax()
+        .into_option()
+        .unwrap_or((0, 0));
+
+    let mut out = String::from("\n");
+    for (rest, active) in slices {
+        out += &format!("--- {:?}\n", rest);
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                out.push(match active.contains(&(x, y)) {
+                    true => '#',
+                    false => '.',
+                });
+            }
+            out.push('\n');
+        }
+    }
+    out += "---";
+    out
+}
+
+/// The boot cycles the puzzle runs.
+pub const CYCLES: usize = 6;
+
+/// The puzzle constants, as in the puzzle unless changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    /// How many boot cycles to run.
+    pub cycles: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { cycles: CYCLES }
+    }
+}
+
+/// Conway cubes: B3/S23 over the Moore neighbourhood in N dimensions.
+pub fn automaton<const N: usize>(initial: &Sparse<Point<N>>) -> Automaton<Sparse<Point<N>>> {
+    let rule = Rule::new(&[3], &[2, 3]);
+    Automaton::new(initial.clone(), Neighbourhood::moore(), rule)
+}
+
+/// The number of active cubes after `cycles` boot cycles; `observe` sees
+/// the initial state and every cycle.
+pub fn run_cycles<const N: usize>(
+    initial: &Sparse<Point<N>>,
+    cycles: usize,
+    mut observe: impl FnMut(&Sparse<Point<N>>),
+) -> usize {
+    let mut automaton = automaton(initial);
+    log::trace!("Grid {}", render(automaton.world()));
+    observe(automaton.world());
+    for iteration in 1..=cycles {
+        automaton.step();
+        log::debug!("Iteration {}: {} active", iteration, automaton.count_live());
+        log::trace!("{}", render(automaton.world()));
+        observe(automaton.world());
+    }
+    automaton.count_live()
+}
+
+// each cycle as every x-y slice side by side, a row of z slices for each w,
+// all drawn to the bounds of every cycle so the frames line up
+fn frames<const N: usize>(initial: &Sparse<Point<N>>, cycles: usize) -> Vec<Frame> {
+    let mut history = Vec::new();
+    run_cycles(initial, cycles, |world| history.push(world.clone()));
+
+    let (mut low, mut high) = ([0; N], [0; N]);
+    for p in history.iter().flat_map(|world| world.live()) {
+        for axis in 0..N {
+            low[axis] = low[axis].min(p.0[axis]);
+            high[axis] = high[axis].max(p.0[axis]);
+        }
+    }
+    let rows = (high[1] - low[1] + 1) as usize;
+    let cols = (high[0] - low[0] + 1) as usize;
+    let ws = match N {
+        3 => 0..=0,
+        _ => low[3]..=high[3],
+    };
+    let colour = |&active: &bool| match active {
+        true => Rgb(0x30, 0x3f, 0x9f),
+        false => Rgb(0xe8, 0xea, 0xf6),
+    };
+
+    history
+        .iter()
+        .map(|world| {
+            let slice = |z, w| {
+                let grid = Grid::from_fn(rows, cols, |pos| {
+                    let mut p = [0; N];
+                    p[0] = low[0] + pos.col;
+                    p[1] = low[1] + pos.row;
+                    p[2] = z;
+                    if let Some(c) = p.get_mut(3) {
+                        *c = w;
+                    }
+                    world.is_live(&Point(p))
+                });
+                Frame::new(&grid, Layout::Square, colour)
+            };
+            let tiles: Vec<Vec<Frame>> = ws
+                .clone()
+                .map(|w| (low[2]..=high[2]).map(|z| slice(z, w)).collect())
+                .collect();
+            Frame::tiled(&tiles, 1, Rgb::WHITE)
+        })
+        .collect()
+}
+
+// the initial slice, embedded in both 3 and 4 dimensions
+#[derive(Serialize, Deserialize)]
+pub struct Pocket {
+    pub grid3: Sparse<Point<3>>,
+    pub grid4: Sparse<Point<4>>,
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Pocket;
+    type Params = Params;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> common::Result<Pocket> {
+        let slice: Slice = input.parse()?;
+        Ok(Pocket {
+            grid3: slice.embed(),
+            grid4: slice.embed(),
+        })
+    }
+
+    fn part1(pocket: &Pocket) -> common::Result<usize> {
+        Self::part1_with(pocket, &Params::default())
+    }
+
+    fn part2(pocket: &Pocket) -> common::Result<usize> {
+        Self::part2_with(pocket, &Params::default())
+    }
+
+    // part 1 -- 3 dimensions
+    fn part1_with(pocket: &Pocket, params: &Params) -> common::Result<usize> {
+        Ok(run_cycles(&pocket.grid3, params.cycles, |_| {}))
+    }
+
+    // part 2 -- 4 dimensions
+    fn part2_with(pocket: &Pocket, params: &Params) -> common::Result<usize> {
+        Ok(run_cycles(&pocket.grid4, params.cycles, |_| {}))
+    }
+}
+
+// the boot process, a cycle at a time; each layer is headed by its other
+// coordinates
+struct Boot<const N: usize> {
+    automaton: Automaton<Sparse<Point<N>>>,
+    cycles: usize,
+}
+
+impl<const N: usize> Simulation for Boot<N> {
+    fn screen(&self) -> String {
+        format!(
+            "cycle {} of {}, {} cubes active\n{}",
+            self.automaton.generation(),
+            self.cycles,
+            self.automaton.count_live(),
+            render(self.automaton.world())
+        )
+    }
+
+    fn step(&mut self) -> bool {
+        if self.automaton.generation() == self.cycles {
+            return false;
+        }
+        self.automaton.step();
+        true
+    }
+}
+
+impl Watch for Day17 {
+    fn simulation(
+        pocket: &Pocket,
+        params: &Params,
+        part: u8,
+    ) -> common::Result<Box<dyn Simulation>> {
+        match part {
+            1 => Ok(Box::new(Boot {
+                automaton: automaton(&pocket.grid3),
+                cycles: params.cycles,
+            })),
+            2 => Ok(Box::new(Boot {
+                automaton: automaton(&pocket.grid4),
+                cycles: params.cycles,
+            })),
+            _ => Err(format!("there is no part {}", part).into()),
+        }
+    }
+}
+
+impl Animate for Day17 {
+    fn frames(pocket: &Pocket, params: &Params, part: u8) -> common::Result<Vec<Frame>> {
+        match part {
+            1 => Ok(frames(&pocket.grid3, params.cycles)),
+            2 => Ok(frames(&pocket.grid4, params.cycles)),
+            _ => Err(format!("there is no part {}", part).into()),
+        }
+    }
+}
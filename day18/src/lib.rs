@@ -1,2 +1,48 @@
+use common::{ParseError, Result, Solution};
+use serde::{Deserialize, Serialize};
+
 pub mod part1;
 pub mod part2;
+
+// where an expression stopped making sense, for either grammar; nom stops
+// before the space preceding the bad term, so skip that
+fn expression_error(s: &str, err: nom::Err<nom::error::Error<&str>>) -> ParseError {
+    match err {
+        nom::Err::Error(e) | nom::Err::Failure(e) => {
+            ParseError::at(s, e.input.trim_start(), "a number, '+', '*' or parentheses")
+        }
+        nom::Err::Incomplete(_) => ParseError::at_end(s, "the rest of the expression"),
+    }
+}
+
+// the two parts use different operator precedence, so the homework is
+// parsed once with each grammar
+#[derive(Serialize, Deserialize)]
+pub struct Homework {
+    pub part1: Vec<part1::Line>,
+    pub part2: Vec<part2::Line>,
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Homework;
+    type Params = ();
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Homework> {
+        Ok(Homework {
+            part1: part1::parse(input)?,
+            part2: part2::parse(input)?,
+        })
+    }
+
+    fn part1(homework: &Homework) -> Result<i64> {
+        Ok(part1::solve(&homework.part1)?)
+    }
+
+    fn part2(homework: &Homework) -> Result<i64> {
+        Ok(part2::solve(&homework.part2)?)
+    }
+}
@@ -1,12 +1,15 @@
-use anyhow::Result;
-use day18::{part1, part2};
+use common::Solution;
+use day18::Day18;
+
+fn main() -> common::Result<()> {
+    common::logging::init_from_env()?;
+    let homework = common::input::parse_from_args::<Day18>(18)?;
 
-fn main() -> Result<()> {
     println!("Part 1 --------");
-    part1::main()?;
+    println!("Total: {}", Day18::part1(&homework)?);
 
     println!("Part 2 --------");
-    part2::main()?;
+    println!("Total: {}", Day18::part2(&homework)?);
 
     Ok(())
 }
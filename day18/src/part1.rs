@@ -1,4 +1,5 @@
 use anyhow::{anyhow, Result};
+use common::{parse, ParseError};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -8,6 +9,7 @@ use nom::{
     sequence::delimited,
     IResult,
 };
+use serde::{Deserialize, Serialize};
 
 extern crate nom;
 
@@ -15,7 +17,7 @@ extern crate nom;
 // https://github.com/Geal/nom/blob/master/doc/choosing_a_combinator.md
 // https://docs.rs/nom/6.0.1/nom/
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 enum Expression {
     Value(i64),
     Add,
@@ -35,11 +37,11 @@ fn reduce(terms: &[Expression]) -> Result<i64> {
             (Expression::Value(v), None) => (value + v, None),
             (Expression::Add, None) => (value, Some(Expression::Add)),
             (Expression::Mul, None) => (value, Some(Expression::Mul)),
-            (Expression::Terms(t), None) => (value + reduce(&t)?, None),
+            (Expression::Terms(t), None) => (value + reduce(t)?, None),
             (Expression::Value(v), Some(Expression::Add)) => (value + v, None),
             (Expression::Value(v), Some(Expression::Mul)) => (value * v, None),
-            (Expression::Terms(t), Some(Expression::Add)) => (value + reduce(&t)?, None),
-            (Expression::Terms(t), Some(Expression::Mul)) => (value * reduce(&t)?, None),
+            (Expression::Terms(t), Some(Expression::Add)) => (value + reduce(t)?, None),
+            (Expression::Terms(t), Some(Expression::Mul)) => (value * reduce(t)?, None),
             _ => return Err(anyhow!("Unexpected state")),
         };
         // remove when destructuring assignment is stabilised
@@ -77,39 +79,41 @@ fn parse_expression(s: &str) -> IResult<&str, Expression> {
     map_res(separated_list1(tag(" "), parse_element), map_terms)(s)
 }
 
-fn parse_program(s: &str) -> Result<Vec<Expression>> {
-    if let Ok(res) = all_consuming(parse_expression)(s) {
-        if let Expression::Terms(terms) = res.1 {
-            Ok(terms)
-        } else {
-            Err(anyhow!("wrong expression type returned"))
-        }
-    } else {
-        Err(anyhow!("parsing failed"))
+fn parse_program(s: &str) -> Result<Vec<Expression>, ParseError> {
+    match all_consuming(parse_expression)(s) {
+        Ok((_, Expression::Terms(terms))) => Ok(terms),
+        Ok(_) => Err(ParseError::at(s, s, "a list of terms")),
+        Err(e) => Err(crate::expression_error(s, e)),
     }
 }
 
-pub fn main() -> Result<()> {
-    //let example_string = "2 * 3 + (4 * 5)";
+// a parsed line of homework, retaining the source for output
+#[derive(Serialize, Deserialize)]
+pub struct Line {
+    source: String,
+    terms: Vec<Expression>,
+}
 
-    // testing
-    println!("{:?}", parse_program("1 + 3 * 5"));
-    println!("{:?}", parse_program("1 + (3 * 4) + (5 + 6)"));
-    println!("{:?}", parse_program("1 + (3 * (4 + 5) + (6 + 7))"));
-    println!("{:?}", reduce(&parse_program("1 + 2")?));
-    println!("{:?}", reduce(&parse_program("1 + 2 * 3")?));
-    println!("{:?}", reduce(&parse_program("2 * (1 + 2)")?));
+// parse every expression (one per line)
+pub fn parse(input: &str) -> Result<Vec<Line>, ParseError> {
+    let input = &parse::normalize(input);
+    parse::lines(input, |l| {
+        Ok(Line {
+            source: l.to_string(),
+            terms: parse_program(l)?,
+        })
+    })
+}
 
+// sum of the results of every expression
+pub fn solve(lines: &[Line]) -> Result<i64> {
     let mut sum = 0i64;
-    let problem_str = std::fs::read_to_string("day18/input.txt")?;
-    for l in problem_str.lines() {
-        let res = reduce(&parse_program(l)?)?;
-        println!("result {} for {}", res, l);
+    for l in lines {
+        let res = reduce(&l.terms)?;
+        log::trace!("result {} for {}", res, l.source);
         sum += res;
     }
-    println!("Total: {}", sum);
-
-    Ok(())
+    Ok(sum)
 }
 
 #[cfg(test)]
@@ -140,4 +144,10 @@ mod tests {
                 .unwrap()
         );
     }
+
+    #[test]
+    fn unexpected_character_located() {
+        let err = parse("1 + 2\n3 * x\n").err().unwrap();
+        assert_eq!((2, 5), (err.line, err.column));
+    }
 }
@@ -1,4 +1,5 @@
 use anyhow::{anyhow, Result};
+use common::{parse, ParseError};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -9,6 +10,7 @@ use nom::{
     sequence::{delimited, pair},
     IResult,
 };
+use serde::{Deserialize, Serialize};
 
 extern crate nom;
 
@@ -16,13 +18,13 @@ extern crate nom;
 // https://github.com/Geal/nom/blob/master/doc/choosing_a_combinator.md
 // https://docs.rs/nom/6.0.1/nom/
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 enum Operation {
     Add,
     Mul,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 enum Expression {
     Value(i64),
     Expr(Box<Expression>, Operation, Box<Expression>),
@@ -99,43 +101,39 @@ fn multiplication_terms(s: &str) -> IResult<&str, Expression> {
     )(s)
 }
 
-fn parse_program(s: &str) -> Result<Expression> {
-    if let Ok((_, res)) = all_consuming(multiplication_terms)(s) {
-        Ok(res)
-    } else {
-        Err(anyhow!("parsing failed"))
-    }
+fn parse_program(s: &str) -> Result<Expression, ParseError> {
+    all_consuming(multiplication_terms)(s)
+        .map(|(_, res)| res)
+        .map_err(|e| crate::expression_error(s, e))
+}
+
+// a parsed line of homework, retaining the source for output
+#[derive(Serialize, Deserialize)]
+pub struct Line {
+    source: String,
+    expression: Expression,
 }
 
-pub fn main() -> Result<()> {
-    // examples
-    println!("{:?}", multiplication_terms("1+2"));
-    println!("{:?}", multiplication_terms("1+2+3*4"));
-    println!("{:?}", multiplication_terms("1*3+2"));
-    println!("{:?}", multiplication_terms("1 * 2 + 3"));
-    println!("{:?}", multiplication_terms("1 + (2 * 3)"));
-    // testing
-    println!("{:?}", parse_program("1 + 3 * 5"));
-    println!("{:?}", parse_program("1 + (3 * 4) + (5 + 6)"));
-    println!("{:?}", parse_program("1 + (3 * (4 + 5) + (6 + 7))"));
-    println!("{:?}", parse_program("1 + 3 * 5")?.reduce());
-    println!("{:?}", parse_program("1 + (3 * 4) + (5 + 6)")?.reduce());
-    println!(
-        "{:?}",
-        parse_program("1 + (3 * (4 + 5) + (6 + 7))")?.reduce()
-    );
-
-    // actual problem
+// parse every expression (one per line)
+pub fn parse(input: &str) -> Result<Vec<Line>, ParseError> {
+    let input = &parse::normalize(input);
+    parse::lines(input, |l| {
+        Ok(Line {
+            source: l.to_string(),
+            expression: parse_program(l)?,
+        })
+    })
+}
+
+// sum of the results of every expression
+pub fn solve(lines: &[Line]) -> Result<i64> {
     let mut sum = 0i64;
-    let problem_str = std::fs::read_to_string("day18/input.txt")?;
-    for l in problem_str.lines() {
-        let res = parse_program(l)?.reduce()?;
-        println!("result {} for {}", res, l);
+    for l in lines {
+        let res = l.expression.reduce()?;
+        log::trace!("result {} for {}", res, l.source);
         sum += res;
     }
-    println!("Total: {}", sum);
-
-    Ok(())
+    Ok(sum)
 }
 
 #[cfg(test)]
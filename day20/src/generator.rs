@@ -0,0 +1,273 @@
+// Jigsaw tiles cut from a random picture of the sea, with a few sea monsters
+// in it.
+//
+// The picture is drawn first, then the borders between the tiles are filled
+// in around it so that no two edges match unless the tiles really are
+// neighbours. Each tile is then turned and flipped at random.
+
+use std::{collections::HashSet, ops::RangeInclusive};
+
+use generate::{Generate, Generated, Rng};
+use grid::{Grid, Pos};
+
+use crate::Day20;
+
+const SEA_MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+// tiles are 10 pixels across, with a one pixel border
+const INNER: usize = crate::TILE_DIM as usize - 2;
+const STEP: usize = INNER + 1;
+
+fn sea_monster() -> Vec<Pos> {
+    let mut body = Vec::new();
+    for (r, row) in SEA_MONSTER.iter().enumerate() {
+        for (c, ch) in row.chars().enumerate() {
+            if ch == '#' {
+                body.push(Pos::new(r as i64, c as i64));
+            }
+        }
+    }
+    body
+}
+
+// `image` turned clockwise `turns` times, then mirrored if `flip`
+fn orient(image: &Grid<bool>, turns: usize, flip: bool) -> Grid<bool> {
+    let n = image.rows() as i64;
+    Grid::from_fn(image.rows(), image.cols(), |p| {
+        let p = match flip {
+            true => Pos::new(p.row, n - 1 - p.col),
+            false => p,
+        };
+        let p = (0..turns).fold(p, |p, _| Pos::new(n - 1 - p.col, p.row));
+        image[p]
+    })
+}
+
+// sea monsters in `sea`, in each orientation
+fn count_sea_monsters(sea: &Grid<bool>) -> Vec<usize> {
+    let body = sea_monster();
+    let (rows, cols) = (SEA_MONSTER.len(), SEA_MONSTER[0].len());
+    (0..8)
+        .map(|o| {
+            let sea = orient(sea, o % 4, o >= 4);
+            let mut count = 0;
+            for r in 0..=sea.rows().saturating_sub(rows) {
+                for c in 0..=sea.cols().saturating_sub(cols) {
+                    let at = Pos::new(r as i64, c as i64);
+                    if body.iter().all(|&p| sea.get(at + p) == Some(&true)) {
+                        count += 1;
+                    }
+                }
+            }
+            count
+        })
+        .collect()
+}
+
+// sparse noise with sea monsters dotted about, none overlapping; tries again
+// until the noise happens not to form any more of them
+fn sea(rng: &mut Rng, dim: usize) -> (Grid<bool>, usize) {
+    let body = sea_monster();
+    let (rows, cols) = (SEA_MONSTER.len(), SEA_MONSTER[0].len());
+    loop {
+        let mut sea = Grid::from_fn(dim, dim, |_| rng.usize(..4) == 0);
+        let mut placed: Vec<Pos> = Vec::new();
+        for _ in 0..dim * dim / 100 + 1 {
+            let at = Pos::new(
+                rng.usize(..=dim - rows) as i64,
+                rng.usize(..=dim - cols) as i64,
+            );
+            let overlaps = placed.iter().any(|q| {
+                (q.row - at.row).abs() < rows as i64 && (q.col - at.col).abs() < cols as i64
+            });
+            if !overlaps {
+                for &p in &body {
+                    sea[at + p] = true;
+                }
+                placed.push(at);
+            }
+        }
+
+        let counts = count_sea_monsters(&sea);
+        if counts[0] == placed.len() && counts[1..].iter().all(|&c| c == 0) {
+            return (sea, placed.len());
+        }
+    }
+}
+
+// the lowest reading of an edge in either direction, or None for the same
+// either way
+fn edge_key(pixels: &[bool]) -> Option<Vec<bool>> {
+    let reversed: Vec<bool> = pixels.iter().rev().copied().collect();
+    match reversed.as_slice().cmp(pixels) {
+        std::cmp::Ordering::Equal => None,
+        std::cmp::Ordering::Less => Some(reversed),
+        std::cmp::Ordering::Greater => Some(pixels.to_vec()),
+    }
+}
+
+// the whole picture, tile borders included; every edge is distinct, even
+// reversed, so neighbours only ever match each other
+fn picture(rng: &mut Rng, sea: &Grid<bool>, side: usize) -> Grid<bool> {
+    let dim = side * STEP + 1;
+    'attempt: loop {
+        let mut picture = Grid::from_fn(dim, dim, |p| {
+            let (r, c) = (p.row as usize, p.col as usize);
+            match (r % STEP, c % STEP) {
+                (0, _) | (_, 0) => rng.bool(),
+                _ => {
+                    sea[Pos::new(
+                        ((r / STEP) * INNER + r % STEP - 1) as i64,
+                        ((c / STEP) * INNER + c % STEP - 1) as i64,
+                    )]
+                }
+            }
+        });
+
+        let mut seen = HashSet::new();
+        for line in 0..=side {
+            for tile in 0..side {
+                for &across in &[true, false] {
+                    let at = |i: usize| match across {
+                        true => Pos::new((line * STEP) as i64, (tile * STEP + i) as i64),
+                        false => Pos::new((tile * STEP + i) as i64, (line * STEP) as i64),
+                    };
+                    let mut tries = 0;
+                    loop {
+                        let pixels: Vec<bool> = (0..=STEP).map(|i| picture[at(i)]).collect();
+                        if let Some(key) = edge_key(&pixels) {
+                            if seen.insert(key) {
+                                break;
+                            }
+                        }
+                        tries += 1;
+                        if tries == 1000 {
+                            continue 'attempt;
+                        }
+                        // the corners are shared with other edges, so only
+                        // the pixels between them change
+                        for i in 1..STEP {
+                            picture[at(i)] = rng.bool();
+                        }
+                    }
+                }
+            }
+        }
+        return picture;
+    }
+}
+
+impl Generate for Day20 {
+    const SIZE_OF: &'static str = "tiles along each side";
+    const DEFAULT_SIZE: usize = 12;
+    // room for a sea monster, and few enough edges to keep them distinct
+    const SIZES: RangeInclusive<usize> = 3..=12;
+
+    fn valid(rng: &mut Rng, side: usize) -> Generated {
+        let (sea, sea_monsters) = sea(rng, side * INNER);
+        let picture = picture(rng, &sea, side);
+
+        let mut ids: Vec<i64> = Vec::new();
+        while ids.len() < side * side {
+            let id = rng.i64(1000..10000);
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+
+        let mut tiles: Vec<String> = Vec::new();
+        for r in 0..side {
+            for c in 0..side {
+                let tile = Grid::from_fn(STEP + 1, STEP + 1, |p| {
+                    picture[p + Pos::new((r * STEP) as i64, (c * STEP) as i64)]
+                });
+                let tile = orient(&tile, rng.usize(..4), rng.bool());
+                tiles.push(format!("Tile {}:\n{}", ids[r * side + c], tile));
+            }
+        }
+        rng.shuffle(&mut tiles);
+
+        let last = side - 1;
+        let corners = ids[0] * ids[last] * ids[last * side] * ids[last * side + last];
+        let roughness = sea.count(|&p| p) - sea_monsters * sea_monster().len();
+        Generated::new(tiles.join("\n"))
+            .with_part1(corners)
+            .with_part2(roughness)
+    }
+
+    fn corrupt(rng: &mut Rng, line: &str) -> String {
+        match line.strip_suffix(':') {
+            Some(header) => header.to_string(),
+            None if rng.bool() => line[1..].to_string(),
+            None => generate::insert_at_random(rng, &line[1..], "o"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+    use generate::{generate, Validity};
+
+    #[test]
+    fn orientations_are_all_different() {
+        let image = Grid::from_fn(3, 3, |p| p.row == 0 || (p.row == 1 && p.col == 0));
+        let all: HashSet<String> = (0..8)
+            .map(|o| orient(&image, o % 4, o >= 4).to_string())
+            .collect();
+        assert_eq!(all.len(), 8);
+        // ###     .##
+        // #..  -> ..#
+        // ...     ..#
+        assert_eq!(orient(&image, 1, false).to_string(), ".##\n..#\n..#\n");
+    }
+
+    #[test]
+    fn generated_tiles_have_the_expected_answers() {
+        for seed in 0..3 {
+            for size in [3, 5] {
+                let generated = generate::<Day20>(seed, size, Validity::Valid).unwrap();
+                let tiles = Day20::parse(&generated.input).unwrap();
+                assert_eq!(tiles.len(), size * size);
+                assert_eq!(
+                    Some(Day20::part1(&tiles).unwrap().to_string()),
+                    generated.part1
+                );
+                assert_eq!(
+                    Some(Day20::part2(&tiles).unwrap().to_string()),
+                    generated.part2
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sizes_out_of_range_are_refused() {
+        for size in [2, 13] {
+            assert!(generate::<Day20>(0, size, Validity::Valid).is_err());
+        }
+    }
+
+    #[test]
+    fn corrupted_tiles_fail_to_parse() {
+        for seed in 0..20 {
+            let generated = generate::<Day20>(seed, 3, Validity::Invalid).unwrap();
+            assert!(
+                Day20::parse(&generated.input).is_err(),
+                "{}",
+                generated.input
+            );
+        }
+    }
+
+    #[test]
+    fn seeds_are_reproducible() {
+        let input = |seed| generate::<Day20>(seed, 3, Validity::Valid).unwrap().input;
+        assert_eq!(input(1), input(1));
+        assert_ne!(input(1), input(2));
+    }
+}
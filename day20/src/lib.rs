@@ -0,0 +1,731 @@
+use common::{parse, Annotated, ParseError, Solution};
+use eyre::Result;
+use grid::{Grid, Pos};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    hash::Hash,
+    ops::{Add, AddAssign},
+};
+
+mod generator;
+
+// Note -- could simplify this quite a bit.
+// Didn't realise how easy the second part of the problem
+// would be, so the zero-allocation rotations stuff is
+// a bit of an overkill. Could just create a physically
+// rotated new image each time, and use the shared
+// grid functions more for slices, etc.
+//
+// Also could use a hashmap<Edge=>Id> instead and match
+// up all the edges like this first, before doing
+// any rotations.
+//
+// Enough time spent on the problem. Works. Moving on...
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct Coord(i32, i32);
+impl Coord {
+    fn rotate(&self, r: &Rotation, dim: i32) -> Self {
+        r.apply(self, dim)
+    }
+}
+impl Display for Coord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({},{})", self.0, self.1)
+    }
+}
+impl Add for Coord {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Coord(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+impl AddAssign for Coord {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+        self.1 += rhs.1;
+    }
+}
+
+// rotate and/or flip
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Rotation {
+    R0,
+    R90,
+    R180,
+    R270,
+    F0,
+    F90,
+    F180,
+    F270,
+}
+impl Rotation {
+    // 90 degree rotation
+    fn cw90(c: &Coord, dim: i32) -> Coord {
+        Coord(dim - 1 - c.1, c.0)
+    }
+    // vertical flip (rows)
+    fn flip(c: &Coord, dim: i32) -> Coord {
+        Coord(dim - 1 - c.0, c.1)
+    }
+
+    fn apply(&self, c: &Coord, dim: i32) -> Coord {
+        let r = |c| Self::cw90(c, dim);
+        let f = |c| Self::flip(c, dim);
+        match self {
+            // rotate only
+            Rotation::R0 => *c,
+            Rotation::R90 => r(c),
+            Rotation::R180 => r(&r(c)),
+            Rotation::R270 => r(&r(&r(c))),
+            // flip and rotate
+            Rotation::F0 => f(c),
+            Rotation::F90 => f(&r(c)),
+            Rotation::F180 => f(&r(&r(c))),
+            Rotation::F270 => f(&r(&r(&r(c)))),
+        }
+    }
+
+    pub fn all() -> &'static [Rotation] {
+        use Rotation::*;
+        &[R0, R90, R180, R270, F0, F90, F180, F270]
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Id(pub i32);
+impl Display for Id {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "id{}", self.0)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Tile {
+    image: Grid<char>,
+    dim: i32,
+    id: Id,
+}
+impl Tile {
+    /// A square tile.
+    pub fn new(id: Id, image: Grid<char>) -> Self {
+        assert_eq!(image.rows(), image.cols(), "tiles are square");
+        Tile {
+            dim: image.rows() as i32,
+            id,
+            image,
+        }
+    }
+
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    pub fn image(&self) -> &Grid<char> {
+        &self.image
+    }
+
+    fn get(&self, c: &Coord) -> Option<char> {
+        self.image.get(Pos::new(c.0 as i64, c.1 as i64)).copied()
+    }
+
+    pub fn rotated(&self, rotation: Rotation) -> RotatedTile<'_> {
+        RotatedTile {
+            tile: self,
+            rotation,
+        }
+    }
+}
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.rotated(Rotation::R0).fmt(f)
+    }
+}
+impl std::fmt::Debug for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f)?;
+        writeln!(f, "Tile-----------------------")?;
+        Display::fmt(self, f)?;
+        writeln!(f, "---------------------------")?;
+        Ok(())
+    }
+}
+
+/// The pixels along a row or column of a rotated tile.
+pub struct TileIterator<'a> {
+    tile: &'a RotatedTile<'a>,
+    current: Coord,
+    inc: Coord,
+}
+impl<'a> Iterator for TileIterator<'a> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let res = self.tile.get(&self.current);
+        self.current += self.inc;
+        res
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Edge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+impl Edge {
+    /// The edge of a neighbouring tile that meets this one.
+    pub fn adjacent(&self) -> Self {
+        use Edge::*;
+        match self {
+            Top => Bottom,
+            Bottom => Top,
+            Left => Right,
+            Right => Left,
+        }
+    }
+    pub fn all() -> &'static [Edge] {
+        &[Edge::Top, Edge::Bottom, Edge::Left, Edge::Right]
+    }
+}
+
+/// A tile seen through a rotation, without copying it.
+pub struct RotatedTile<'a> {
+    tile: &'a Tile,
+    rotation: Rotation,
+}
+
+impl<'a> RotatedTile<'a> {
+    fn get(&self, c: &Coord) -> Option<char> {
+        self.tile.get(&c.rotate(&self.rotation, self.tile.dim))
+    }
+
+    fn row_iter(&self, row: i32) -> TileIterator<'_> {
+        TileIterator {
+            tile: self,
+            current: Coord(row, 0),
+            inc: Coord(0, 1),
+        }
+    }
+    fn col_iter(&self, col: i32) -> TileIterator<'_> {
+        TileIterator {
+            tile: self,
+            current: Coord(0, col),
+            inc: Coord(1, 0),
+        }
+    }
+
+    pub fn edge_iter(&self, edge: Edge) -> TileIterator<'_> {
+        let far_index = self.tile.dim - 1;
+        match edge {
+            Edge::Top => self.row_iter(0),
+            Edge::Bottom => self.row_iter(far_index),
+            Edge::Left => self.col_iter(0),
+            Edge::Right => self.col_iter(far_index),
+        }
+    }
+
+    fn render(&self, range: &std::ops::Range<i32>) -> Tile {
+        let offset = range.start;
+        let image = Grid::from_fn(range.len(), range.len(), |p| {
+            self.get(&Coord(offset + p.row as i32, offset + p.col as i32))
+                .unwrap()
+        });
+        Tile {
+            dim: range.len() as i32,
+            id: self.tile.id,
+            image,
+        }
+    }
+
+    pub fn render_borderless_tile(&self) -> Tile {
+        let range = 1..self.tile.dim - 1;
+        self.render(&range)
+    }
+
+    pub fn render_tile(&self) -> Tile {
+        let range = 0..self.tile.dim;
+        self.render(&range)
+    }
+}
+
+impl<'a> Display for RotatedTile<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for r in 0..self.tile.dim {
+            for c in 0..self.tile.dim {
+                let ch = self.get(&Coord(r, c)).ok_or(std::fmt::Error)?;
+                write!(f, "{}", ch)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+// debug == display; too verbose otherwise
+impl<'a> std::fmt::Debug for RotatedTile<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f)?;
+        writeln!(f, "RotatedTile----------------")?;
+        writeln!(f, "{:?}", self.rotation)?;
+        Display::fmt(self, f)?;
+        writeln!(f, "---------------------------")?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct TileMap<'a> {
+    tiles: HashMap<Id, TileRelation<'a>>,
+    all_ids: Vec<Id>,
+}
+
+#[derive(Debug)]
+struct TileRelation<'a> {
+    tile: &'a Tile,
+    rotated: Option<RotatedTile<'a>>,
+    neighbours: HashMap<Edge, Id>,
+}
+
+#[derive(Debug)]
+struct FoundRelation {
+    ref_edge: Edge,
+    other_id: Id,
+    other_new_rotation: Option<Rotation>,
+}
+
+impl<'a> TileMap<'a> {
+    fn create(tiles: &'a [Tile]) -> Self {
+        let mut tile_map = HashMap::new();
+        for t in tiles.iter() {
+            let relation = TileRelation {
+                tile: t,
+                rotated: None,
+                neighbours: HashMap::new(),
+            };
+            tile_map.insert(t.id, relation);
+        }
+        let all_ids = tile_map.keys().copied().collect();
+        TileMap {
+            tiles: tile_map,
+            all_ids,
+        }
+    }
+
+    fn get_relation(&self, id: &Id) -> Option<&TileRelation<'_>> {
+        self.tiles.get(id)
+    }
+
+    // solve once, and return true if something was done
+    fn solve_one(&self, all_ids: &[Id], reference_id: Id) -> Option<FoundRelation> {
+        let ref_relation = self.tiles.get(&reference_id).unwrap();
+        let ref_rotated = ref_relation.rotated.as_ref().unwrap();
+        for this_edge in Edge::all() {
+            // already have a relationship; skip
+            if ref_relation.neighbours.contains_key(this_edge) {
+                continue;
+            }
+            // find a new relationship
+            for other_id in all_ids {
+                if *other_id == reference_id {
+                    continue; // skip self
+                }
+                let other_rel = self.tiles.get(other_id).unwrap();
+                if let Some(other_rotated) = &other_rel.rotated {
+                    // already rotated -- just check and associate if found
+                    let adjacent_edge = this_edge.adjacent();
+                    if !other_rel.neighbours.contains_key(&adjacent_edge)
+                        && itertools::equal(
+                            ref_rotated.edge_iter(*this_edge),
+                            other_rotated.edge_iter(adjacent_edge),
+                        )
+                    {
+                        return Some(FoundRelation {
+                            other_id: other_rel.tile.id,
+                            ref_edge: *this_edge,
+                            other_new_rotation: None,
+                        });
+                    }
+                } else {
+                    // not rotated -- find a rotation
+                    let adjacent_edge = this_edge.adjacent();
+                    for rotation in Rotation::all() {
+                        let other_rotated = other_rel.tile.rotated(*rotation);
+                        if itertools::equal(
+                            ref_rotated.edge_iter(*this_edge),
+                            other_rotated.edge_iter(adjacent_edge),
+                        ) {
+                            return Some(FoundRelation {
+                                other_id: other_rel.tile.id,
+                                ref_edge: *this_edge,
+                                other_new_rotation: Some(*rotation),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn solve(&mut self) {
+        let all_ids = self.all_ids.clone();
+
+        // lock rotation of first tile (need to start somewhere)
+        {
+            let t = self.tiles.get_mut(&all_ids[0]).unwrap();
+            t.rotated = Some(t.tile.rotated(Rotation::R0));
+        }
+
+        loop {
+            let mut changed = false;
+            for this_id in all_ids.iter() {
+                // skip this if the rotation is not set yet
+                if self.tiles.get(this_id).unwrap().rotated.is_none() {
+                    log::trace!("Skipping {} as rotation not set", this_id);
+                    continue;
+                }
+                // otherwise find a new relationship and record it
+                if let Some(new_relation) = self.solve_one(&all_ids, *this_id) {
+                    log::debug!("new relation: {} => {:?}", this_id, new_relation);
+
+                    // this -> other
+                    let this_rel = self.tiles.get_mut(this_id).unwrap();
+                    this_rel
+                        .neighbours
+                        .insert(new_relation.ref_edge, new_relation.other_id);
+
+                    // other -> this (and set rotation if found)
+                    let other_rel = self.tiles.get_mut(&new_relation.other_id).unwrap();
+                    other_rel
+                        .neighbours
+                        .insert(new_relation.ref_edge.adjacent(), *this_id);
+                    if let Some(new_rotation) = new_relation.other_new_rotation {
+                        other_rel.rotated = Some(other_rel.tile.rotated(new_rotation))
+                    }
+
+                    // record change occurred
+                    changed = true;
+                }
+            }
+            // converged
+            if !changed {
+                break;
+            }
+        }
+
+        // found
+        log::trace!("{:?}", &self);
+    }
+}
+
+// quick n dirty
+pub fn parse_tiles(contents: &str, dim: i32) -> Result<Vec<Tile>, ParseError> {
+    let contents = &parse::normalize(contents);
+    let mut tiles = Vec::new();
+    let mut lines = contents.lines();
+
+    while let Some(l) = lines.next() {
+        // tiles are separated by blank lines
+        if l.is_empty() {
+            continue;
+        }
+
+        let id_str = parse::tag(contents, l, "Tile ")?;
+        let id_str = id_str
+            .strip_suffix(':')
+            .ok_or_else(|| ParseError::at(contents, l, "a header like \"Tile 2311:\""))?;
+        let id: i32 = parse::number(contents, id_str)?;
+        let mut image = Grid::new(dim as usize, dim as usize, 'X');
+        for r in 0..dim {
+            let row_str = lines.next().ok_or_else(|| {
+                ParseError::at_end(contents, format!("{} rows for tile {}", dim, id))
+            })?;
+            if row_str.chars().count() != dim as usize {
+                return Err(ParseError::at(
+                    contents,
+                    row_str,
+                    format!("a row of {} pixels", dim),
+                ));
+            }
+            for (c, (i, ch)) in row_str.char_indices().enumerate() {
+                if ch != '.' && ch != '#' {
+                    return Err(ParseError::at(contents, &row_str[i..], "'.' or '#'"));
+                }
+                image[Pos::new(r as i64, c as i64)] = ch;
+            }
+        }
+
+        let tile = Tile {
+            image,
+            id: Id(id),
+            dim,
+        };
+        tiles.push(tile);
+    }
+
+    if tiles.is_empty() {
+        return Err(ParseError::at_end(contents, "at least one tile"));
+    }
+    Ok(tiles)
+}
+
+pub const TILE_DIM: i32 = 10;
+
+/// The ids of the four corner tiles of the arranged image.
+pub fn corner_ids(tiles: &[Tile]) -> common::Result<Vec<i32>> {
+    for t in tiles {
+        log::trace!("id {:?}\n{}", t.id, &t);
+    }
+
+    let mut tile_map = TileMap::create(tiles);
+    tile_map.solve();
+
+    // check all are oriented
+    if !tile_map.tiles.values().all(|tr| tr.rotated.is_some()) {
+        return Err("not all tiles could be oriented".into());
+    }
+
+    // find the 4 corners
+    let corners: Vec<_> = tile_map
+        .tiles
+        .values()
+        .filter(|&tr| tr.neighbours.len() == 2)
+        .collect();
+    for tr in corners.iter() {
+        log::info!("{} has {:?}", tr.tile.id, tr.neighbours);
+    }
+    Ok(corners.iter().map(|tr| tr.tile.id.0).collect())
+}
+
+/// Arranges the tiles, and joins them without their borders into one image.
+pub fn assemble(tiles: &[Tile]) -> common::Result<Tile> {
+    let dim = tiles.first().ok_or("no tiles to assemble")?.dim;
+    let mut tile_map = TileMap::create(tiles);
+    tile_map.solve();
+
+    // get top row of tiles, then create a big tile with rendered contents
+    let top_row = {
+        let mut top_row = Vec::new();
+        let top_left = tile_map
+            .tiles
+            .iter()
+            .find(|(_, tr)| {
+                !tr.neighbours.contains_key(&Edge::Left) && !tr.neighbours.contains_key(&Edge::Top)
+            })
+            .ok_or("no top-left tile")?;
+        let mut current = top_left.1;
+        top_row.push(current);
+        while let Some(next) = current.neighbours.get(&Edge::Right) {
+            current = tile_map.get_relation(next).unwrap();
+            top_row.push(current);
+        }
+        top_row
+    };
+    log::debug!("{:?}", top_row);
+
+    // copy little tiles into one large tile
+    let rendered_dim = dim as usize - 2;
+    let dest_dim = rendered_dim * top_row.len();
+    let mut big_tile = Tile {
+        dim: dest_dim as i32,
+        id: Id(0),
+        image: Grid::new(dest_dim, dest_dim, 'X'),
+    };
+    let mut row = top_row;
+    let mut row_number = 0;
+    loop {
+        // copy into the big tile
+        for (col_number, tr) in row.iter().enumerate() {
+            let rendered = tr.rotated.as_ref().unwrap().render_borderless_tile();
+            let r0 = row_number * rendered_dim;
+            let c0 = col_number * rendered_dim;
+            big_tile
+                .image
+                .paste(&rendered.image, Pos::new(r0 as i64, c0 as i64));
+        }
+        // and get next row (until none are left)
+        let next_row: Option<Vec<&TileRelation>> = row
+            .iter()
+            .map(|tr| {
+                tr.neighbours
+                    .get(&Edge::Bottom)
+                    .map(|id| tile_map.get_relation(id).unwrap())
+            })
+            .collect();
+        if let Some(next_row) = next_row {
+            row = next_row;
+            row_number += 1;
+        } else {
+            break;
+        }
+    }
+    Ok(big_tile)
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Vec<Tile>;
+    type Params = ();
+    type Part1 = Annotated<i64>;
+    type Part2 = Annotated<usize>;
+
+    fn parse(input: &str) -> common::Result<Vec<Tile>> {
+        Ok(parse_tiles(input, TILE_DIM)?)
+    }
+
+    // as parsed: at least one tile, each a square of TILE_DIM pixels
+    fn validate(tiles: &Vec<Tile>) -> common::Result<()> {
+        if tiles.is_empty() {
+            return Err("no tiles".into());
+        }
+        for t in tiles {
+            let (rows, cols) = (t.image.rows() as i32, t.image.cols() as i32);
+            if t.dim != TILE_DIM || rows != TILE_DIM || cols != TILE_DIM {
+                return Err(format!("tile {} isn't {} pixels square", t.id.0, TILE_DIM).into());
+            }
+            if t.image.iter().any(|(_, &c)| c != '.' && c != '#') {
+                return Err(format!("tile {} has pixels other than '.' and '#'", t.id.0).into());
+            }
+        }
+        Ok(())
+    }
+
+    // ----------------
+    // Part 1
+    //
+    fn part1(tiles: &Vec<Tile>) -> common::Result<Annotated<i64>> {
+        let corner_ids = corner_ids(tiles)?;
+        let product: i64 = corner_ids.iter().map(|&id| id as i64).product();
+        Ok(Annotated::new(product).with("corner_ids", corner_ids))
+    }
+
+    // ----------------
+    // Part 2 - arrange the tiles, then find the seamonster
+    //
+    fn part2(tiles: &Vec<Tile>) -> common::Result<Annotated<usize>> {
+        let big_tile = assemble(tiles)?;
+        let dest_dim = big_tile.dim as usize;
+        log::trace!("Big tile\n{}", big_tile.rotated(Rotation::F0));
+
+        // construct our seamonster
+        let seamonster = {
+            let seamonster_data = [
+                "                  # ",
+                "#    ##    ##    ###",
+                " #  #  #  #  #  #   ",
+            ];
+            Grid::<char>::parse(&seamonster_data.join("\n"))?
+        };
+        log::trace!("{:?}", seamonster);
+
+        // find the seamonster (kind of like convolution) -- try all rotations until
+        // we find some :)
+        // only the '#' parts of the seamonster matter; the spaces match anything
+        let body: Vec<Pos> = seamonster
+            .iter()
+            .filter(|(_, &m)| m == '#')
+            .map(|(p, _)| p)
+            .collect();
+        for rot in Rotation::all() {
+            let search_tile = big_tile.rotated(*rot).render_tile();
+            let mut destination_tile = search_tile.clone();
+            let mut seamonster_count = 0;
+            for r_start in 0..=(dest_dim - seamonster.rows()) {
+                for c_start in 0..=(dest_dim - seamonster.cols()) {
+                    let window = Pos::new(r_start as i64, c_start as i64);
+                    // detect if any part of the seamonster is missing
+                    let found_seamonster =
+                        body.iter().all(|&p| search_tile.image[window + p] == '#');
+                    if found_seamonster {
+                        seamonster_count += 1;
+                        // set destination seamonster elements to 'O'
+                        for &p in &body {
+                            destination_tile.image[window + p] = 'O';
+                        }
+                    }
+                }
+            }
+
+            // found 'em
+            if seamonster_count > 0 {
+                let sea_roughness = destination_tile.image.count(|&ch| ch == '#');
+
+                log::info!(
+                    "Found {} seamonsters; roughness is {}; result is\n{}",
+                    seamonster_count,
+                    sea_roughness,
+                    destination_tile
+                );
+                return Ok(Annotated::new(sea_roughness)
+                    .with("seamonsters", seamonster_count)
+                    .with("rotation", format!("{:?}", rot)));
+            }
+        }
+
+        Err("no seamonsters found in any rotation".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // all chars:
+    //  123
+    //  456
+    //  789
+    fn create_tile() -> Tile {
+        Tile {
+            id: Id(1234),
+            dim: 3,
+            image: Grid::parse("123\n456\n789").unwrap(),
+        }
+    }
+
+    #[test]
+    fn rotation_correct() {
+        let tile = create_tile();
+        let t0 = tile.rotated(Rotation::R0);
+        let t1 = tile.rotated(Rotation::R90);
+        let t2 = tile.rotated(Rotation::R180);
+        let t3 = tile.rotated(Rotation::R270);
+
+        itertools::assert_equal("123".chars(), t0.row_iter(0));
+        itertools::assert_equal("741".chars(), t1.row_iter(0));
+        itertools::assert_equal("987".chars(), t2.row_iter(0));
+        itertools::assert_equal("369".chars(), t3.row_iter(0));
+    }
+
+    #[test]
+    fn edge_correct() {
+        let tile = create_tile();
+        let t0 = tile.rotated(Rotation::R0);
+
+        itertools::assert_equal("123".chars(), t0.edge_iter(Edge::Top));
+        itertools::assert_equal("789".chars(), t0.edge_iter(Edge::Bottom));
+        itertools::assert_equal("147".chars(), t0.edge_iter(Edge::Left));
+        itertools::assert_equal("369".chars(), t0.edge_iter(Edge::Right));
+    }
+
+    #[test]
+    fn tiles_parse_from_crlf_with_stray_blank_lines() {
+        let tiles = parse_tiles(
+            "Tile 1:\r\n#.#\r\n...\r\n##. \r\n \r\n\r\nTile 2:\r\n...\r\n.#.\r\n...\r\n\r\n\r\n",
+            3,
+        )
+        .unwrap();
+        let ids: Vec<_> = tiles.iter().map(|t| t.id().0).collect();
+        assert_eq!(ids, vec![1, 2]);
+        itertools::assert_equal("##.".chars(), tiles[0].rotated(Rotation::R0).row_iter(2));
+
+        let e = parse_tiles("Tile 1:\r\n#.#\r\n", 3).unwrap_err();
+        assert_eq!(e.expected, "3 rows for tile 1");
+
+        let e = parse_tiles("\n\n", 3).unwrap_err();
+        assert_eq!(e.expected, "at least one tile");
+    }
+}
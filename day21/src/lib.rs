@@ -1 +1,139 @@
 pub mod parse;
+
+use std::collections::{HashMap, HashSet};
+
+use common::{ParseError, Solution};
+use eyre::{eyre, Result};
+use parse::parse_food;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Food {
+    pub ingredients: HashSet<String>,
+    pub allergens: HashSet<String>,
+}
+impl Food {
+    pub fn parse_line(i: &str) -> Result<Food, ParseError> {
+        let (_rem, res) = parse_food(i).map_err(|e| match e {
+            nom::Err::Error(e) | nom::Err::Failure(e) => ParseError::at(
+                i,
+                e.input,
+                "a food like \"mxmxvkd kfcds (contains dairy, fish)\"",
+            ),
+            nom::Err::Incomplete(_) => ParseError::at_end(i, "the rest of the food"),
+        })?;
+        Ok(Food {
+            ingredients: res.0.iter().map(|s| s.to_string()).collect(),
+            allergens: res.1.iter().map(|s| s.to_string()).collect(),
+        })
+    }
+}
+
+pub fn parse_foods(contents: &str) -> Result<Vec<Food>, ParseError> {
+    let contents = &common::parse::normalize(contents);
+    let foods = common::parse::lines(contents, Food::parse_line)?;
+    log::trace!("{:?}", foods);
+    Ok(foods)
+}
+
+// for each allergen, the ingredients present in every food that lists it
+pub fn possible_causes(foods: &[Food]) -> Result<HashMap<&String, HashSet<String>>> {
+    let all_allergens: HashSet<_> = foods.iter().flat_map(|f| f.allergens.iter()).collect();
+
+    let mut possible_causes = HashMap::new();
+    for allergen in all_allergens {
+        let mut matching_foods = foods.iter().filter(|f| f.allergens.contains(allergen));
+        let possible_ingredients = matching_foods
+            .next()
+            .map(|m| matching_foods.fold(m.ingredients.clone(), |acc, f| &acc & &f.ingredients))
+            .ok_or_else(|| eyre!("no foods contain {}", allergen))?;
+
+        possible_causes.insert(allergen, possible_ingredients);
+    }
+
+    log::debug!("Possible causes {:?}", possible_causes);
+    Ok(possible_causes)
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Vec<Food>;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = String;
+
+    fn parse(input: &str) -> common::Result<Vec<Food>> {
+        Ok(parse_foods(input)?)
+    }
+
+    // -----------------------------
+    // Part 1 -- find inert ingredients
+    fn part1(foods: &Vec<Food>) -> common::Result<usize> {
+        let possible_causes = possible_causes(foods)?;
+
+        let all_ingredients: HashSet<_> = foods.iter().flat_map(|f| f.ingredients.iter()).collect();
+        let all_ingredients_possible_causes: HashSet<_> = possible_causes
+            .values()
+            .flat_map(|ingred| ingred.iter())
+            .collect();
+        let safe_ingredients: HashSet<_> = all_ingredients
+            .iter()
+            .filter(|&ingredient| !all_ingredients_possible_causes.contains(ingredient))
+            .collect();
+
+        log::debug!("Safe ingredients: {:?}", safe_ingredients);
+        let safe_ingredients_count = safe_ingredients.iter().fold(0, |acc, &&i| {
+            let count = foods.iter().filter(|&f| f.ingredients.contains(i)).count();
+            acc + count
+        });
+        Ok(safe_ingredients_count)
+    }
+
+    // -----------------------------
+    // Part 2 -- find which ingredients map to which allergens
+    fn part2(foods: &Vec<Food>) -> common::Result<String> {
+        let mut possible_reduction = possible_causes(foods)?;
+
+        loop {
+            // create list of known unique causes (cloning them to avoid mutability collisions)
+            let unique_causes: Vec<_> = possible_reduction
+                .iter()
+                .filter_map(|(&allergen, ingredients)| match ingredients.len() {
+                    1 => Some((allergen.clone(), ingredients.iter().next().unwrap().clone())),
+                    _ => None,
+                })
+                .collect();
+
+            // remove each of these ingredients from the list of other possible causes of allergens
+            for (unique_allergen, unique_ingredient) in unique_causes.iter() {
+                for (&allergen, ingredients) in possible_reduction.iter_mut() {
+                    if allergen != unique_allergen {
+                        ingredients.remove(unique_ingredient);
+                    }
+                }
+            }
+
+            // stop when all allergens have a unique cause
+            if possible_reduction
+                .iter()
+                .all(|(_, ingredients)| ingredients.len() == 1)
+            {
+                break;
+            }
+        }
+
+        // collect and sort by allergen
+        let mut causes: Vec<_> = possible_reduction
+            .iter()
+            .map(|(&allergen, ingredients)| (allergen.clone(), ingredients.iter().next().unwrap()))
+            .collect();
+        causes.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+
+        log::info!("Unique causes: {:?}", causes);
+
+        // and present as a canonical list of allergens
+        let canonical = itertools::join(causes.iter().map(|(_, ingred)| ingred), ",");
+        Ok(canonical)
+    }
+}
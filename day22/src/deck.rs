@@ -1,6 +1,7 @@
-use std::collections::VecDeque;
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, fmt};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Deck {
     pub player: i32,
     pub cards: VecDeque<i32>,
@@ -19,3 +20,11 @@ impl Deck {
             .sum()
     }
 }
+
+impl fmt::Display for Deck {
+    // as in the puzzle: "Player 1's deck: 9, 2, 6"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cards: Vec<String> = self.cards.iter().map(|c| c.to_string()).collect();
+        write!(f, "Player {}'s deck: {}", self.player, cards.join(", "))
+    }
+}
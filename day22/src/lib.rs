@@ -2,3 +2,100 @@ pub mod deck;
 pub mod parser;
 pub mod part1;
 pub mod part2;
+
+use common::{Result, Solution};
+use part1::SimpleGame;
+use part2::{RecursiveGame, RecursiveMatch};
+use viewer::{Simulation, Watch};
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = SimpleGame;
+    type Params = ();
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<SimpleGame> {
+        Ok(parser::parse_input(input)?)
+    }
+
+    // part 1 -- winning player's score
+    fn part1(simple_game: &SimpleGame) -> Result<i32> {
+        let mut game = simple_game.clone();
+        log::debug!("game: {:?}", game);
+
+        while !game.is_complete() {
+            game.next_round();
+            log::trace!("game: {:?}", game);
+        }
+
+        log::info!("Player 1 score: {}", game.player1.score());
+        log::info!("Player 2 score: {}", game.player2.score());
+        // the loser's deck is empty, and scores zero
+        Ok(game.player1.score().max(game.player2.score()))
+    }
+
+    // part 2 -- winning player's score in recursive combat
+    fn part2(simple_game: &SimpleGame) -> Result<i32> {
+        let mut game = RecursiveGame::from_simple_game(simple_game);
+
+        let winner = game.play_game();
+        let deck = game.deck_for(winner);
+        log::info!("Winner {:?} {:?}", winner, deck);
+        Ok(deck.score())
+    }
+}
+
+// part 1 a round at a time
+struct Combat {
+    game: SimpleGame,
+    round: usize,
+}
+
+impl Simulation for Combat {
+    fn screen(&self) -> String {
+        let mut lines = vec![
+            format!("-- Round {} --", self.round),
+            self.game.player1.to_string(),
+            self.game.player2.to_string(),
+        ];
+        if self.game.is_complete() {
+            let score = self.game.player1.score().max(self.game.player2.score());
+            lines.push(format!("game over, winning score {}", score));
+        }
+        lines.join("\n")
+    }
+
+    fn step(&mut self) -> bool {
+        if self.game.is_complete() {
+            return false;
+        }
+        self.game.next_round();
+        self.round += 1;
+        true
+    }
+}
+
+impl Simulation for RecursiveMatch {
+    fn screen(&self) -> String {
+        RecursiveMatch::screen(self)
+    }
+
+    fn step(&mut self) -> bool {
+        RecursiveMatch::step(self)
+    }
+}
+
+impl Watch for Day22 {
+    fn simulation(game: &SimpleGame, _params: &(), part: u8) -> Result<Box<dyn Simulation>> {
+        match part {
+            1 => Ok(Box::new(Combat {
+                game: game.clone(),
+                round: 0,
+            })),
+            2 => Ok(Box::new(RecursiveMatch::new(game))),
+            _ => Err(format!("there is no part {}", part).into()),
+        }
+    }
+}
@@ -1,8 +1,8 @@
-use eyre::eyre;
+use common::ParseError;
 use nom::{
     bytes::complete::tag,
     character::complete::*,
-    combinator::{map, map_res, recognize},
+    combinator::{all_consuming, map, map_res, recognize},
     multi::*,
     sequence::*,
     IResult,
@@ -32,15 +32,28 @@ fn parse_deck(i: &str) -> IResult<&str, Deck> {
     )(i)
 }
 
-pub fn parse_input(i: &str) -> eyre::Result<SimpleGame> {
-    if let Ok((_rem, mut res)) = separated_list1(multispace1, parse_deck)(i) {
-        let d2 = res.pop().ok_or_else(|| eyre!("missing deck 2"))?;
-        let d1 = res.pop().ok_or_else(|| eyre!("missing deck 1"))?;
-        Ok(SimpleGame {
-            player1: d1,
-            player2: d2,
-        })
-    } else {
-        Err(eyre!("failed to parse"))
+pub fn parse_input(i: &str) -> Result<SimpleGame, ParseError> {
+    let i = &common::parse::normalize(i);
+    let decks = all_consuming(terminated(
+        separated_list1(multispace1, parse_deck),
+        multispace0,
+    ))(i);
+    match decks {
+        Ok((_rem, res)) => {
+            let mut decks = res.into_iter();
+            match (decks.next(), decks.next(), decks.next()) {
+                (Some(d1), Some(d2), None) => Ok(SimpleGame {
+                    player1: d1,
+                    player2: d2,
+                }),
+                _ => Err(ParseError::at(i, i, "decks for exactly two players")),
+            }
+        }
+        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => Err(ParseError::at(
+            i,
+            e.input,
+            "\"Player N:\" and then one card per line",
+        )),
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::at_end(i, "the rest of the decks")),
     }
 }
@@ -1,6 +1,7 @@
 use crate::deck::Deck;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SimpleGame {
     pub player1: Deck,
     pub player2: Deck,
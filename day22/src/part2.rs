@@ -1,20 +1,27 @@
-use std::usize;
-
 use crate::{deck::Deck, part1::SimpleGame};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Player {
     Player1,
     Player2,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+fn higher_card(c1: i32, c2: i32) -> Player {
+    if c1 >= c2 {
+        Player::Player1
+    } else {
+        Player::Player2
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GameState {
     player1: Deck,
     player2: Deck,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RecursiveGame {
     state: GameState,
     history: Vec<GameState>,
@@ -66,66 +73,178 @@ impl RecursiveGame {
         self.state.player1.cards.is_empty() || self.state.player2.cards.is_empty()
     }
 
-    pub fn play_game(&mut self) -> Player {
-        while !self.is_complete() {
-            // check history; player 1 wins if we've been in this state before.
-            if self.history.contains(&self.state) {
-                return Player::Player1;
+    // the winner, if this game is over before the next round
+    fn game_winner(&self) -> Option<Player> {
+        if self.is_complete() {
+            match self.state.player1.cards.is_empty() {
+                true => Some(Player::Player2),
+                false => Some(Player::Player1),
             }
+        } else if self.history.contains(&self.state) {
+            // player 1 wins if we've been in this state before.
+            Some(Player::Player1)
+        } else {
+            None
+        }
+    }
 
-            // record history
-            self.history.push(self.state.clone());
+    // record history, and take cards
+    fn draw(&mut self) -> (i32, i32) {
+        self.history.push(self.state.clone());
+        let c1 = self.state.player1.cards.pop_front().unwrap();
+        let c2 = self.state.player2.cards.pop_front().unwrap();
+        (c1, c2)
+    }
 
-            // take cards
-            let c1 = self.state.player1.cards.pop_front().unwrap();
-            let c2 = self.state.player2.cards.pop_front().unwrap();
+    // If both players have at least as many cards remaining in their deck as
+    // the value of the card they just drew, the winner of the round is
+    // determined by playing a new game of Recursive Combat (see below).
+    fn subgame_for(&self, c1: i32, c2: i32) -> Option<Self> {
+        let remaining_c1 = self.state.player1.cards.len() as i32;
+        let remaining_c2 = self.state.player2.cards.len() as i32;
+        if remaining_c1 >= c1 && remaining_c2 >= c2 {
+            Some(self.create_subgame(c1 as usize, c2 as usize))
+        } else {
+            None
+        }
+    }
 
-            // If both players have at least as many cards remaining in their deck as
-            // the value of the card they just drew, the winner of the round is
-            // determined by playing a new game of Recursive Combat (see below).
-            let remaining_c1 = self.state.player1.cards.len() as i32;
-            let remaining_c2 = self.state.player2.cards.len() as i32;
-            let winner = if remaining_c1 >= c1 && remaining_c2 >= c2 {
+    // put cards on the winner's deck; winner's card first.
+    fn award(&mut self, winner: Player, c1: i32, c2: i32) {
+        match winner {
+            Player::Player1 => {
+                self.state.player1.cards.push_back(c1);
+                self.state.player1.cards.push_back(c2);
+            }
+            Player::Player2 => {
+                self.state.player2.cards.push_back(c2);
+                self.state.player2.cards.push_back(c1);
+            }
+        }
+    }
+
+    pub fn play_game(&mut self) -> Player {
+        loop {
+            if let Some(winner) = self.game_winner() {
+                return winner;
+            }
+
+            let (c1, c2) = self.draw();
+            let winner = match self.subgame_for(c1, c2) {
                 // play subgame
-                let mut subgame = self.create_subgame(c1 as usize, c2 as usize);
-                subgame.play_game()
-            } else {
+                Some(mut subgame) => subgame.play_game(),
                 // not enough cards to recurse: winner is the player with
                 // the higher value card
-                if c1 >= c2 {
-                    Player::Player1
-                } else {
-                    Player::Player2
-                }
+                None => higher_card(c1, c2),
             };
+            self.award(winner, c1, c2);
+        }
+    }
 
-            // put cards on the winner's deck; winner's card first.
-            match winner {
-                Player::Player1 => {
-                    self.state.player1.cards.push_back(c1);
-                    self.state.player1.cards.push_back(c2);
+    pub fn deck_for(&self, player: Player) -> &Deck {
+        match player {
+            Player::Player1 => &self.state.player1,
+            Player::Player2 => &self.state.player2,
+        }
+    }
+}
+
+// a game in progress, as one level of a match
+#[derive(Debug)]
+struct Level {
+    game: RecursiveGame,
+    number: usize,
+    round: usize,
+    // the cards riding on a subgame in progress
+    drawn: Option<(i32, i32)>,
+}
+
+/// Recursive Combat played a round at a time, for watching it: a stack of
+/// the games in progress, each waiting on the subgame above it.
+#[derive(Debug)]
+pub struct RecursiveMatch {
+    levels: Vec<Level>,
+    games_played: usize,
+    winner: Option<Player>,
+}
+
+impl RecursiveMatch {
+    pub fn new(game: &SimpleGame) -> Self {
+        RecursiveMatch {
+            levels: vec![Level {
+                game: RecursiveGame::from_simple_game(game),
+                number: 1,
+                round: 0,
+                drawn: None,
+            }],
+            games_played: 1,
+            winner: None,
+        }
+    }
+
+    /// Plays a round of the innermost game, or ends it; false once the
+    /// match is over.
+    pub fn step(&mut self) -> bool {
+        if self.winner.is_some() {
+            return false;
+        }
+        let level = self.levels.last_mut().unwrap();
+
+        if let Some(winner) = level.game.game_winner() {
+            let finished = self.levels.pop().unwrap();
+            match self.levels.last_mut() {
+                Some(parent) => {
+                    let (c1, c2) = parent.drawn.take().unwrap();
+                    parent.game.award(winner, c1, c2);
                 }
-                Player::Player2 => {
-                    self.state.player2.cards.push_back(c2);
-                    self.state.player2.cards.push_back(c1);
+                None => {
+                    self.winner = Some(winner);
+                    // keep the final decks on show
+                    self.levels.push(finished);
                 }
             }
+            return true;
         }
 
-        if self.state.player1.cards.is_empty() {
-            Player::Player2
-        } else if self.state.player2.cards.is_empty() {
-            Player::Player1
-        } else {
-            panic!("end of game: one player's deck should be empty")
+        level.round += 1;
+        let (c1, c2) = level.game.draw();
+        match level.game.subgame_for(c1, c2) {
+            Some(subgame) => {
+                level.drawn = Some((c1, c2));
+                self.games_played += 1;
+                self.levels.push(Level {
+                    game: subgame,
+                    number: self.games_played,
+                    round: 0,
+                    drawn: None,
+                });
+            }
+            None => level.game.award(higher_card(c1, c2), c1, c2),
         }
+        true
     }
 
-    pub fn deck_for(&self, player: Player) -> &Deck {
-        match player {
-            Player::Player1 => &self.state.player1,
-            Player::Player2 => &self.state.player2,
+    pub fn winner(&self) -> Option<Player> {
+        self.winner
+    }
+
+    pub fn screen(&self) -> String {
+        let mut lines = Vec::new();
+        if let Some(level) = self.levels.last() {
+            lines.push(format!(
+                "-- Game {} (depth {}), round {} --",
+                level.number,
+                self.levels.len(),
+                level.round
+            ));
+            lines.push(level.game.state.player1.to_string());
+            lines.push(level.game.state.player2.to_string());
+        }
+        if let Some(winner) = self.winner {
+            let score = self.levels[0].game.deck_for(winner).score();
+            lines.push(format!("{:?} wins, scoring {}", winner, score));
         }
+        lines.join("\n")
     }
 }
 
@@ -133,7 +252,7 @@ impl RecursiveGame {
 mod tests {
     use crate::deck::Deck;
 
-    use super::{GameState, Player, RecursiveGame};
+    use super::{GameState, Player, RecursiveGame, RecursiveMatch};
 
     #[test]
     fn validate_breakout() {
@@ -155,4 +274,18 @@ mod tests {
         // assert
         assert_eq!(Player::Player1, game.play_game())
     }
+
+    #[test]
+    fn match_steps_to_the_same_result() {
+        let input = std::fs::read_to_string("example-input.txt").unwrap();
+        let simple = crate::parser::parse_input(&input).unwrap();
+
+        let mut game = RecursiveGame::from_simple_game(&simple);
+        let winner = game.play_game();
+
+        let mut stepped = RecursiveMatch::new(&simple);
+        while stepped.step() {}
+        assert_eq!(Some(winner), stepped.winner());
+        assert!(stepped.screen().ends_with("Player2 wins, scoring 291"));
+    }
 }
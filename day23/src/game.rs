@@ -1,6 +1,7 @@
 use std::{collections::HashMap, fmt::Display};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 struct Node {
     addr: usize, 
     next_addr: Option<usize>,
@@ -8,13 +9,16 @@ struct Node {
     value: i32,
 }
 
-struct CircularList {
+/// Cup labels in a circle, linked by position so moving a run of cups is
+/// cheap, with an index from label to position.
+#[derive(Serialize, Deserialize)]
+pub struct CircularList {
     nodes: Vec<Node>,
     value_map: HashMap<i32, usize>
 }
 
 impl CircularList {
-    fn create(values: &[i32]) -> Self {
+    pub fn create(values: &[i32]) -> Self {
         let mut nodes = Vec::new();
         let mut value_map = HashMap::new();
         let mut prev_addr = None;
@@ -25,7 +29,7 @@ impl CircularList {
                 addr,
                 value: v,
                 next_addr: None,
-                prev_addr: prev_addr.clone(),
+                prev_addr,
             };
             nodes.push(node);
             // link prev node to this
@@ -54,11 +58,11 @@ impl CircularList {
         self.nodes.get_mut(addr)
     }
 
-    fn find_value(&self, value:i32) -> Option<usize> {
+    pub fn find_value(&self, value:i32) -> Option<usize> {
         self.value_map.get(&value).copied()
     }
 
-    fn move_chain(&mut self, chain_start: usize, chain_length: usize, attach_after: usize) {
+    pub fn move_chain(&mut self, chain_start: usize, chain_length: usize, attach_after: usize) {
         // walk along chain to find end address
         let chain_end = (0..chain_length-1).fold(chain_start, |acc,_| self.get(acc).unwrap().next_addr.unwrap());
 
@@ -81,7 +85,7 @@ impl CircularList {
         }
     }
 
-    fn copy_values(&self, destination: &mut[i32], chain_start: usize) {
+    pub fn copy_values(&self, destination: &mut[i32], chain_start: usize) {
         let mut next_addr = chain_start;
         for d in destination.iter_mut() {
             let node = self.get(next_addr).unwrap();
@@ -90,7 +94,7 @@ impl CircularList {
         }
     }
 
-    fn copy_all_values(&self, chain_start: usize) -> Vec<i32> {
+    pub fn copy_all_values(&self, chain_start: usize) -> Vec<i32> {
         let mut res = vec![0; self.nodes.len()];
         self.copy_values(&mut res, chain_start);
         res
@@ -99,8 +103,10 @@ impl CircularList {
 
 
 
-const CHAIN_LENGTH: usize = 3;
-struct Game {
+/// The cups each move picks up.
+pub const CHAIN_LENGTH: usize = 3;
+#[derive(Serialize, Deserialize)]
+pub struct Game {
     state: CircularList,
     current_value: i32,
     current_addr: usize,
@@ -111,7 +117,7 @@ struct Game {
 
 impl Game {
     fn create_internal(initial_vector: Vec<i32>) -> Game {
-        let current_value = *initial_vector.get(0).unwrap();
+        let current_value = *initial_vector.first().unwrap();
         let min = *initial_vector.iter().min().unwrap();
         let max = *initial_vector.iter().max().unwrap();
         Game {
@@ -124,14 +130,14 @@ impl Game {
         }
     }
 
-    fn create_part1(initial_vector: &[i32]) -> Game {
-        let v: Vec<i32> = initial_vector.iter().copied().collect();
+    pub fn create_part1(initial_vector: &[i32]) -> Game {
+        let v: Vec<i32> = initial_vector.to_vec();
         Self::create_internal(v)
     }
 
     // initialise for part 2 -- take the initial vector, then proceed by adding 1 until we
     // reach the total number of cups specified.
-    fn create_part2(initial_vector: &[i32], total_cups: usize) -> Game {
+    pub fn create_part2(initial_vector: &[i32], total_cups: usize) -> Game {
         let remaining_start = initial_vector.iter().max().unwrap() + 1;
         let remaining_range = remaining_start..;
 
@@ -158,7 +164,7 @@ impl Game {
         }
     }
 
-    fn play_round(&mut self) {
+    pub fn play_round(&mut self) {
         //use std::time::Instant;
 
         // let t0 = Instant::now();
@@ -185,6 +191,14 @@ impl Game {
         self.current_addr = next_addr;
         self.current_value = next_value;
     }
+
+    pub fn current_value(&self) -> i32 {
+        self.current_value
+    }
+
+    pub fn cups(&self) -> &CircularList {
+        &self.state
+    }
 }
 
 impl Display for Game {
@@ -197,58 +211,112 @@ impl Display for Game {
     }
 }
 
+// a game played a few moves at a time, for watching it
+pub struct Crab {
+    game: Game,
+    moves: usize,
+    total_moves: usize,
+    moves_per_step: usize,
+}
+
+// cups shown around the current cup, when there are too many to show all of them
+const WINDOW: usize = 20;
+
+impl Crab {
+    pub fn part1(init: &[i32], total_moves: usize) -> Crab {
+        Crab { game: Game::create_part1(init), moves: 0, total_moves, moves_per_step: 1 }
+    }
+
+    // ten million moves are too many to watch one by one
+    pub fn part2(init: &[i32], total_cups: usize, total_moves: usize) -> Crab {
+        Crab { game: Game::create_part2(init, total_cups), moves: 0, total_moves, moves_per_step: 10_000 }
+    }
+
+    // plays the next few moves; false once all have been played
+    pub fn step(&mut self) -> bool {
+        if self.moves == self.total_moves {
+            return false;
+        }
+        let moves = self.moves_per_step.min(self.total_moves - self.moves);
+        for _ in 0..moves {
+            self.game.play_round();
+        }
+        self.moves += moves;
+        true
+    }
+
+    // as in the puzzle: "cups: (3) 8  9  1  2  5  4  6  7", starting from the current cup
+    pub fn screen(&self) -> String {
+        let count = self.game.state.nodes.len();
+        let mut cups = vec![0; count.min(WINDOW)];
+        self.game.state.copy_values(&mut cups, self.game.current_addr);
+        let mut labels: Vec<String> = cups.iter().map(|c| c.to_string()).collect();
+        labels[0] = format!("({})", labels[0]);
+        if count > WINDOW {
+            labels.push("...".to_string());
+        }
+
+        let mut after_one = vec![0; CHAIN_LENGTH];
+        self.game.state.copy_values(&mut after_one, self.game.state.find_value(1).unwrap());
+        let lines = [
+            format!("-- move {} of {} --", self.moves, self.total_moves),
+            format!("cups: {}", labels.join(" ")),
+            format!("pick up: {:?}", &cups[1..CHAIN_LENGTH + 1]),
+            format!("after cup 1: {:?}", &after_one[1..]),
+        ];
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
 pub fn vec_from_chars(s: &str) -> Vec<i32> {
     s.chars().map(|c| c.to_string().parse().unwrap()).collect()
 }
 
-pub fn test_part1() {
-    let init = vec_from_chars("389125467");
-    let mut game = Game::create_part1(&init);
+// labels on the cups after cup 1, after playing the given number of rounds
+pub fn run_part1(init: &[i32], rounds: usize) -> String {
+    let mut game = Game::create_part1(init);
 
-    println!("start -> game {}", game);
-    for round in 0..10 {
+    log::debug!("start -> game {}", game);
+    for round in 0..rounds {
         game.play_round();
-        println!("round {} game {}", round, game);
+        log::trace!("round {} game {}", round, game);
     }
-    println!("Final result {:?}", game.state.copy_all_values(game.state.find_value(1).unwrap()))
+
+    let values = game.state.copy_all_values(game.state.find_value(1).unwrap());
+    log::info!("Final result {:?}", values);
+    values.iter().skip(1).map(|v| v.to_string()).collect()
 }
 
-fn run_part2(init_state: &str) -> i64 {
-    let init = vec_from_chars(init_state);
-    let mut game = Game::create_part2(&init, 1_000_000);
+// product of the two cups after cup 1, after filling the circle up to the
+// given number of cups and playing the given number of rounds
+pub fn run_part2(init: &[i32], total_cups: usize, rounds: usize) -> i64 {
+    let mut game = Game::create_part2(init, total_cups);
 
-    println!("start -> game {}", game);
-    for round in 0..10_000_000 {
+    log::trace!("start -> game {}", game);
+    for round in 0..rounds {
         game.play_round();
         if round % 1_000_000 == 0 {
-            println!("round {} game {}", round, game);
+            log::debug!("round {}", round);
+            log::trace!("game {}", game);
         }
     }
-    println!("final game {}", game);
+    log::trace!("final game {}", game);
 
     let one_addr = game.state.find_value(1).unwrap();
     let mut res = vec![0;3];
     game.state.copy_values(&mut res, one_addr);
 
-    println!("result: {:?}", res);
+    log::info!("result: {:?}", res);
     let product = res[1] as i64 * res[2] as i64;
-    println!("product of [1]*[2] => {}", product); 
+    log::debug!("product of [1]*[2] => {}", product); 
     product
 }
 
-pub fn test_part2() {
-    let product = run_part2("389125467");
-    assert_eq!(149245887792, product);
-}
-
-pub fn actual_part2() {
-    let product = run_part2("963275481");
-    println!("final result {}", product);
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     fn create() -> CircularList {
         CircularList::create(&[7, 2, 5, 8, 9, 1, 3, 4, 6]) // 9 total
@@ -312,6 +380,28 @@ mod tests {
         assert_eq!(vec![4, 6, 7], buffer);
     }
 
+    #[test]
+    fn test_part1_labels() {
+        assert_eq!("92658374", run_part1(&vec_from_chars("389125467"), 10));
+        assert_eq!("67384529", run_part1(&vec_from_chars("389125467"), 100));
+    }
+
+    #[test]
+    fn test_part2_product() {
+        assert_eq!(149245887792, run_part2(&vec_from_chars("389125467"), 1_000_000, 10_000_000));
+    }
+
+    #[test]
+    fn crab_shows_the_current_cup() {
+        let mut crab = Crab::part1(&vec_from_chars("389125467"), 10);
+        assert!(crab.screen().contains("cups: (3) 8 9 1 2 5 4 6 7"));
+        assert!(crab.step());
+        assert!(crab.screen().contains("cups: (2) 8 9 1 5 4 6 7 3"));
+        while crab.step() {}
+        assert!(crab.screen().starts_with("-- move 10 of 10 --"));
+        assert!(crab.screen().contains("after cup 1: [9, 2]"));
+    }
+
     #[test]
     fn test_part1_expected() {
         let expected_sequence = [
@@ -340,4 +430,71 @@ mod tests {
             );
         }
     }
+
+    // the cups in a plain vector, rotated so the current cup is always first
+    fn play_round_by_rotation(cups: &mut Vec<i32>) {
+        let current = cups[0];
+        let picked: Vec<i32> = cups.drain(1..=CHAIN_LENGTH).collect();
+        let min = *cups.iter().min().unwrap().min(picked.iter().min().unwrap());
+        let max = *cups.iter().max().unwrap().max(picked.iter().max().unwrap());
+        let mut destination = current;
+        loop {
+            destination = if destination == min {
+                max
+            } else {
+                destination - 1
+            };
+            if !picked.contains(&destination) {
+                break;
+            }
+        }
+        let at = cups.iter().position(|&c| c == destination).unwrap() + 1;
+        cups.splice(at..at, picked);
+        cups.rotate_left(1);
+    }
+
+    // a shuffled run of consecutive labels, as the crab's cups are
+    fn cups() -> impl Strategy<Value = Vec<i32>> {
+        (1..4i32, 5..20i32).prop_flat_map(|(first, count)| {
+            Just((first..first + count).collect::<Vec<_>>()).prop_shuffle()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn game_matches_rotating_a_vector(init in cups(), rounds in 0..60usize) {
+            let mut game = Game::create_part1(&init);
+            let mut model = init.clone();
+            for _ in 0..rounds {
+                game.play_round();
+                play_round_by_rotation(&mut model);
+                let current = game.cups().find_value(game.current_value()).unwrap();
+                prop_assert_eq!(game.cups().copy_all_values(current), model.clone());
+            }
+        }
+
+        #[test]
+        fn moved_chain_matches_vector_splice(
+            init in cups(),
+            start in 0..20usize,
+            length in 1..4usize,
+            after in 0..20usize,
+        ) {
+            let n = init.len();
+            let chain: Vec<i32> = (0..length).map(|i| init[(start + i) % n]).collect();
+            let after = init[after % n];
+            prop_assume!(!chain.contains(&after));
+
+            let mut list = CircularList::create(&init);
+            let chain_start = list.find_value(chain[0]).unwrap();
+            list.move_chain(chain_start, length, list.find_value(after).unwrap());
+
+            let mut model: Vec<i32> = init.iter().copied().filter(|c| !chain.contains(c)).collect();
+            let at = model.iter().position(|&c| c == after).unwrap() + 1;
+            model.splice(at..at, chain.iter().copied());
+            // both read from the cup the chain went after
+            model.rotate_left(at - 1);
+            prop_assert_eq!(list.copy_all_values(list.find_value(after).unwrap()), model);
+        }
+    }
 }
@@ -0,0 +1,161 @@
+use common::{ParseError, Result, Solution};
+use serde::{Deserialize, Serialize};
+use viewer::{Simulation, Watch};
+
+pub mod game;
+
+/// The puzzle constants, as in the puzzle unless changed. The starting cups
+/// are the input itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    /// How many moves part 1 plays.
+    pub part1_moves: usize,
+    /// How many cups part 2 fills the circle up to.
+    pub part2_cups: usize,
+    /// How many moves part 2 plays.
+    pub part2_moves: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            part1_moves: 100,
+            part2_cups: 1_000_000,
+            part2_moves: 10_000_000,
+        }
+    }
+}
+
+// part 2 adds cups to the ones given, never takes any away
+fn part2_cups(cups: &[i32], params: &Params) -> Result<usize> {
+    match params.part2_cups >= cups.len() {
+        true => Ok(params.part2_cups),
+        false => Err(format!(
+            "part 2 needs at least the {} cups given, not {}",
+            cups.len(),
+            params.part2_cups
+        )
+        .into()),
+    }
+}
+
+/// The fewest cups a game can be played with: the ones a move picks up, and
+/// the current cup.
+pub const MIN_CUPS: usize = game::CHAIN_LENGTH + 1;
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Vec<i32>;
+    type Params = Params;
+    type Part1 = String;
+    type Part2 = i64;
+
+    // cup labels are single digits, each of 1 to the number of cups once;
+    // a move picks up three cups and needs another to put them after
+    fn parse(input: &str) -> Result<Vec<i32>> {
+        let labels = input.trim();
+        let count = labels.chars().count() as u32;
+        if count < MIN_CUPS as u32 {
+            let expected = format!("at least {} cup labels", MIN_CUPS);
+            return Err(ParseError::at_end(input, expected).into());
+        }
+
+        let mut cups = Vec::new();
+        for (i, c) in labels.char_indices() {
+            match c.to_digit(10) {
+                Some(d) if d >= 1 && d <= count && !cups.contains(&(d as i32)) => {
+                    cups.push(d as i32)
+                }
+                _ => {
+                    let expected = format!("a distinct cup label from 1 to {}", count);
+                    return Err(ParseError::at(input, &labels[i..], expected).into());
+                }
+            }
+        }
+        Ok(cups)
+    }
+
+    // as parsed: single digit labels, each of 1 to the number of cups once,
+    // and enough of them for a move
+    fn validate(cups: &Vec<i32>) -> Result<()> {
+        let mut sorted = cups.clone();
+        sorted.sort_unstable();
+        let expected: Vec<i32> = (1..=cups.len() as i32).collect();
+        if cups.len() < MIN_CUPS {
+            return Err(format!(
+                "a move needs at least {} cups, not {}",
+                MIN_CUPS,
+                cups.len()
+            )
+            .into());
+        }
+        if cups.len() > 9 || sorted != expected {
+            return Err(format!("cups {:?} aren't labelled 1 to {}", cups, cups.len()).into());
+        }
+        Ok(())
+    }
+
+    fn part1(cups: &Vec<i32>) -> Result<String> {
+        Self::part1_with(cups, &Params::default())
+    }
+
+    fn part2(cups: &Vec<i32>) -> Result<i64> {
+        Self::part2_with(cups, &Params::default())
+    }
+
+    // part 1 -- labels after cup 1, after 100 moves
+    fn part1_with(cups: &Vec<i32>, params: &Params) -> Result<String> {
+        Ok(game::run_part1(cups, params.part1_moves))
+    }
+
+    // part 2 -- product of the two cups after cup 1, with a million cups and ten million moves
+    fn part2_with(cups: &Vec<i32>, params: &Params) -> Result<i64> {
+        let total_cups = part2_cups(cups, params)?;
+        Ok(game::run_part2(cups, total_cups, params.part2_moves))
+    }
+}
+
+impl Simulation for game::Crab {
+    fn screen(&self) -> String {
+        game::Crab::screen(self)
+    }
+
+    fn step(&mut self) -> bool {
+        game::Crab::step(self)
+    }
+}
+
+impl Watch for Day23 {
+    fn simulation(cups: &Vec<i32>, params: &Params, part: u8) -> Result<Box<dyn Simulation>> {
+        match part {
+            1 => Ok(Box::new(game::Crab::part1(cups, params.part1_moves))),
+            2 => {
+                let total_cups = part2_cups(cups, params)?;
+                Ok(Box::new(game::Crab::part2(
+                    cups,
+                    total_cups,
+                    params.part2_moves,
+                )))
+            }
+            _ => Err(format!("there is no part {}", part).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn too_few_cups_rejected() {
+        for input in ["1", "12", "123"] {
+            let e = Day23::parse(input).unwrap_err();
+            let e = e.downcast::<ParseError>().unwrap();
+            assert_eq!("at least 4 cup labels", e.expected);
+        }
+        assert!(Day23::validate(&vec![1, 2, 3]).is_err());
+        assert_eq!(vec![1, 2, 3, 4], Day23::parse("1234").unwrap());
+    }
+}
@@ -1,11 +1,19 @@
-use eyre::Result;
+use common::Solution;
+use day23::Day23;
 
-mod game;
+fn main() -> common::Result<()> {
+    common::logging::init_from_env()?;
+    let cups = common::input::parse_from_args::<Day23>(23)?;
+    let params = common::params::from_args::<Day23>(23)?;
 
-fn main() -> Result<()> {
-    game::test_part1();
-    game::test_part2();
-    game::actual_part2();
+    println!(
+        "Part 1 -> labels after cup 1: {}",
+        Day23::part1_with(&cups, &params)?
+    );
+    println!(
+        "Part 2 -> final result {}",
+        Day23::part2_with(&cups, &params)?
+    );
 
     Ok(())
 }
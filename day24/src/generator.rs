@@ -0,0 +1,135 @@
+// Lines of directions to tiles scattered over a hexagon about the size of the
+// input; a tenth of them go back to an earlier tile, flipping it again. Each
+// line heads for its tile with a few detours that cancel out.
+
+use std::collections::HashMap;
+
+use generate::{Generate, Generated, Rng};
+
+use crate::Day24;
+
+// cube coordinates of each step, independent of `Dir::coord`
+const STEPS: [(&str, [i32; 3]); 6] = [
+    ("e", [1, -1, 0]),
+    ("se", [0, -1, 1]),
+    ("sw", [-1, 0, 1]),
+    ("w", [-1, 1, 0]),
+    ("nw", [0, 1, -1]),
+    ("ne", [1, 0, -1]),
+];
+
+fn distance(tile: [i32; 3]) -> i32 {
+    tile.iter().map(|c| c.abs()).max().unwrap_or(0)
+}
+
+// steps from the reference tile to `tile`, in a random order
+fn path(rng: &mut Rng, tile: [i32; 3]) -> Vec<usize> {
+    let mut steps = Vec::new();
+    let mut left = tile;
+    while left != [0; 3] {
+        let closer: Vec<usize> = (0..STEPS.len())
+            .filter(|&s| {
+                let [x, y, z] = STEPS[s].1;
+                distance([left[0] - x, left[1] - y, left[2] - z]) < distance(left)
+            })
+            .collect();
+        let s = closer[rng.usize(..closer.len())];
+        let [x, y, z] = STEPS[s].1;
+        left = [left[0] - x, left[1] - y, left[2] - z];
+        steps.push(s);
+    }
+    // a line has at least one step, even to the reference tile
+    let detours = usize::from(steps.is_empty());
+    for _ in 0..rng.usize(detours..=3) {
+        let s = rng.usize(..STEPS.len());
+        steps.push(s);
+        steps.push((s + STEPS.len() / 2) % STEPS.len());
+    }
+    rng.shuffle(&mut steps);
+    steps
+}
+
+impl Generate for Day24 {
+    const SIZE_OF: &'static str = "lines";
+    const DEFAULT_SIZE: usize = 500;
+
+    fn valid(rng: &mut Rng, size: usize) -> Generated {
+        // a hexagon of radius r has 3r(r + 1) + 1 tiles, about twice the lines
+        let radius = ((2 * size / 3) as f64).sqrt().ceil() as i32;
+        let mut tiles: Vec<[i32; 3]> = Vec::new();
+        let mut lines = Vec::new();
+        for _ in 0..size {
+            let tile = match tiles.is_empty() || rng.usize(..10) > 0 {
+                true => {
+                    let x = rng.i32(-radius..=radius);
+                    let y = rng.i32((-radius).max(-x - radius)..=radius.min(radius - x));
+                    [x, y, -x - y]
+                }
+                false => tiles[rng.usize(..tiles.len())],
+            };
+            tiles.push(tile);
+            lines.push(path(rng, tile));
+        }
+
+        let mut flips: HashMap<[i32; 3], usize> = HashMap::new();
+        for line in &lines {
+            let tile = line.iter().fold([0; 3], |t, &s| {
+                let [x, y, z] = STEPS[s].1;
+                [t[0] + x, t[1] + y, t[2] + z]
+            });
+            *flips.entry(tile).or_default() += 1;
+        }
+        let black = flips.values().filter(|&&n| n % 2 == 1).count();
+
+        let text: Vec<String> = lines
+            .iter()
+            .map(|l| l.iter().map(|&s| STEPS[s].0).collect())
+            .collect();
+        Generated::new(text.join("\n") + "\n").with_part1(black)
+    }
+
+    fn corrupt(rng: &mut Rng, line: &str) -> String {
+        generate::insert_at_random(rng, line, "x")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Solution;
+    use generate::{generate, Validity};
+
+    #[test]
+    fn generated_lines_have_the_expected_answers() {
+        for seed in 0..10 {
+            let generated = generate::<Day24>(seed, 100, Validity::Valid).unwrap();
+            let lines = Day24::parse(&generated.input).unwrap();
+            assert_eq!(lines.len(), 100);
+            assert_eq!(
+                Some(Day24::part1(&lines).unwrap().to_string()),
+                generated.part1
+            );
+            let black: usize = generated.part1.unwrap().parse().unwrap();
+            assert!(black > 40 && black < 100, "{}", black);
+        }
+    }
+
+    #[test]
+    fn corrupted_lines_fail_to_parse() {
+        for seed in 0..20 {
+            let generated = generate::<Day24>(seed, 20, Validity::Invalid).unwrap();
+            assert!(
+                Day24::parse(&generated.input).is_err(),
+                "{}",
+                generated.input
+            );
+        }
+    }
+
+    #[test]
+    fn seeds_are_reproducible() {
+        let input = |seed| generate::<Day24>(seed, 20, Validity::Valid).unwrap().input;
+        assert_eq!(input(1), input(1));
+        assert_ne!(input(1), input(2));
+    }
+}
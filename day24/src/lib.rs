@@ -1,9 +1,20 @@
 use std::ops::{Add, AddAssign, Index};
+
+use common::{parse, ParseError, Solution};
+use eyre::Result;
+use grid::{
+    automaton::{Automaton, Neighbourhood, Rule, State},
+    Grid, Pos, Topology,
+};
+use parser::directions;
+use render::{Animate, Frame, Layout, Rgb};
+use serde::{Deserialize, Serialize};
 use strum_macros::EnumIter;
 
+mod generator;
 pub mod parser;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, Serialize, Deserialize)]
 pub enum Dir {
     NW,
     NE,
@@ -27,7 +38,7 @@ impl Dir {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub struct Coord([i32; 2]);
 
 impl Coord {
@@ -88,6 +99,197 @@ pub fn fold_directions(directions: &[Dir]) -> Coord {
         .fold(Coord::default(), |acc, d| acc + d.coord())
 }
 
+pub fn parse_input(input: &str) -> Result<Vec<Vec<Dir>>, ParseError> {
+    let input = &parse::normalize(input);
+    parse::lines(input, |l| {
+        match nom::combinator::all_consuming(directions)(l) {
+            Ok((_, dirs)) => Ok(dirs),
+            Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => Err(ParseError::at(
+                l,
+                e.input,
+                "a direction: e, se, sw, w, nw or ne",
+            )),
+            Err(nom::Err::Incomplete(_)) => Err(ParseError::at_end(l, "a direction")),
+        }
+    })
+}
+
+// axial coordinates on the grid: x along the columns, y along the rows, so
+// the six directions are exactly the grid's hex neighbours
+impl From<Coord> for Pos {
+    fn from(c: Coord) -> Self {
+        Pos::new(c[1] as i64, c[0] as i64)
+    }
+}
+
+/// The floor as a dense grid of tiles, `true` for black, with room around
+/// the initial flips for the pattern to grow into.
+#[derive(Serialize, Deserialize)]
+pub struct Floor {
+    pub tiles: Grid<bool>,
+}
+
+impl Floor {
+    // the pattern grows by at most one tile a day, so a margin of `days`
+    // tiles is enough to evolve that many days without reaching the edge
+    pub fn flip_tiles(all_directions: &[Vec<Dir>], days: usize) -> Floor {
+        let coords: Vec<Pos> = all_directions
+            .iter()
+            .map(|dirs| fold_directions(dirs).into())
+            .collect();
+
+        // find bounding box around flipped tiles, expanded by the margin
+        let margin = Pos::new(days as i64, days as i64);
+        let low = coords.iter().fold(Pos::default(), |low, p| {
+            Pos::new(low.row.min(p.row), low.col.min(p.col))
+        }) - margin;
+        let high = coords.iter().fold(Pos::default(), |high, p| {
+            Pos::new(high.row.max(p.row), high.col.max(p.col))
+        }) + margin;
+        let size = high - low + Pos::new(1, 1);
+
+        let mut tiles = Grid::new(size.row as usize, size.col as usize, false);
+        for c in coords {
+            tiles[c - low] ^= true;
+        }
+        Floor { tiles }
+    }
+
+    pub fn count_black(&self) -> usize {
+        self.tiles.count(|&v| v)
+    }
+
+    // a black tile with zero or more than two black neighbours flips to
+    // white, and a white tile with exactly two flips to black
+    pub fn evolution(&self) -> Automaton<Grid<State>> {
+        let world = self.tiles.map(|&black| match black {
+            true => State::Live,
+            false => State::Dead,
+        });
+        let rule = Rule::new(&[2], &[1, 2]);
+        Automaton::new(world, Neighbourhood::adjacent(Topology::Hex), rule)
+    }
+}
+
+/// The days the puzzle lets the floor evolve.
+pub const DAYS: usize = 100;
+
+/// The puzzle constants, as in the puzzle unless changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    /// How many days part 2 lets the floor evolve.
+    pub days: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { days: DAYS }
+    }
+}
+
+/// The most days part 2 evolves the floor. The floor is a dense grid with a
+/// margin of a tile a day on every side, so its area, and the time a day
+/// takes, grows with the square of the days.
+pub const MAX_DAYS: usize = 500;
+
+// the days to evolve, if the floor for that many fits
+fn days(params: &Params) -> common::Result<usize> {
+    match params.days <= MAX_DAYS {
+        true => Ok(params.days),
+        false => Err(format!("at most {} days, not {}", MAX_DAYS, params.days).into()),
+    }
+}
+
+// evolve the floor for `days` days, showing `observe` each day's floor,
+// starting with the initial flips
+pub fn exhibit(
+    all_directions: &[Vec<Dir>],
+    days: usize,
+    mut observe: impl FnMut(&Grid<State>),
+) -> usize {
+    let floor = Floor::flip_tiles(all_directions, days);
+    log::debug!("black tiles: {}", floor.count_black());
+
+    let mut evolution = floor.evolution();
+    observe(evolution.world());
+    for day in 1..=days {
+        evolution.step();
+        log::debug!("day {} black tiles: {}", day, evolution.count_live());
+        observe(evolution.world());
+    }
+    evolution.count_live()
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Vec<Vec<Dir>>;
+    type Params = Params;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> common::Result<Vec<Vec<Dir>>> {
+        Ok(parse_input(input)?)
+    }
+
+    // part 1 -- black tiles after the initial flips
+    fn part1(all_directions: &Vec<Vec<Dir>>) -> common::Result<usize> {
+        // for dirs in &all_directions {
+        //     let coord = fold_directions(&dirs);
+        //     println!("{:?} from {:?}", coord, dirs);
+        // }
+
+        let floor = Floor::flip_tiles(all_directions, 0);
+        log::trace!("flips:\n{}", floor.tiles);
+        Ok(floor.count_black())
+    }
+
+    fn part2(all_directions: &Vec<Vec<Dir>>) -> common::Result<usize> {
+        Self::part2_with(all_directions, &Params::default())
+    }
+
+    // part 2 -- black tiles after 100 days of evolution
+    fn part2_with(all_directions: &Vec<Vec<Dir>>, params: &Params) -> common::Result<usize> {
+        Ok(exhibit(all_directions, days(params)?, |_| {}))
+    }
+}
+
+// black and white tiles on a grey background
+fn tile_colour(black: bool) -> Rgb {
+    match black {
+        true => Rgb(0x21, 0x21, 0x21),
+        false => Rgb(0xfa, 0xfa, 0xfa),
+    }
+}
+
+// part 1 is the initial flips; part 2 the floor on each day
+impl Animate for Day24 {
+    fn frames(
+        all_directions: &Vec<Vec<Dir>>,
+        params: &Params,
+        part: u8,
+    ) -> common::Result<Vec<Frame>> {
+        let grey = Rgb(0x9e, 0x9e, 0x9e);
+        match part {
+            1 => {
+                let floor = Floor::flip_tiles(all_directions, 0);
+                let frame = Frame::new(&floor.tiles, Layout::Hex, |&v| tile_colour(v));
+                Ok(vec![frame.with_background(grey)])
+            }
+            2 => {
+                let mut frames = Vec::new();
+                exhibit(all_directions, days(params)?, |world| {
+                    let frame = Frame::new(world, Layout::Hex, |&s| tile_colour(s == State::Live));
+                    frames.push(frame.with_background(grey))
+                });
+                Ok(frames)
+            }
+            _ => Err(format!("there is no part {}", part).into()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -101,6 +303,33 @@ mod tests {
         assert_eq!(Coord::default(), c);
     }
 
+    #[test]
+    fn example_part1_correct() {
+        let input = include_str!("../example-input.txt");
+        let directions = Day24::parse(input).unwrap();
+        assert_eq!(10, Day24::part1(&directions).unwrap());
+    }
+
+    #[test]
+    fn example_part2_correct() {
+        let input = include_str!("../example-input.txt");
+        let directions = Day24::parse(input).unwrap();
+        assert_eq!(2208, Day24::part2(&directions).unwrap());
+    }
+
+    #[test]
+    fn too_many_days_refused() {
+        let directions = Day24::parse("esew\n").unwrap();
+        let params = Params { days: MAX_DAYS + 1 };
+        assert!(Day24::part2_with(&directions, &params).is_err());
+    }
+
+    #[test]
+    fn bad_direction_located() {
+        let err = parse_input("esew\nnwwnxe\n").unwrap_err();
+        assert_eq!((2, 4, "nxe"), (err.line, err.column, err.found.as_str()));
+    }
+
     #[test]
     fn directions_adjacent() {
         let d = directions("esew").unwrap().1;
@@ -0,0 +1,156 @@
+use common::{parse, ParseError, Result, Solution};
+use serde::{Deserialize, Serialize};
+
+pub const DIVISOR: i64 = 20201227;
+
+/// The subject number the puzzle's public keys are made from.
+pub const SUBJECT: i64 = 7;
+
+/// The puzzle constants, as in the puzzle unless changed. The public keys are
+/// the input itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Params {
+    /// The subject number the public keys were made from.
+    pub subject: i64,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Params { subject: SUBJECT }
+    }
+}
+
+pub fn transform_once(number: i64, subject_number: i64) -> i64 {
+    let n = number * subject_number;
+    n % DIVISOR
+}
+
+/// `subject_number` transformed `loop_size` times.
+pub fn transform_number(subject_number: i64, loop_size: usize) -> i64 {
+    let mut number = 1;
+    for _ in 0..loop_size {
+        number = transform_once(number, subject_number);
+    }
+    number
+}
+
+/// The loop size that transforms `subject_number` into `target_public_key`,
+/// if any does; the transformed numbers repeat within `DIVISOR` loops.
+pub fn find_loop_size(subject_number: i64, target_public_key: i64) -> Option<usize> {
+    let mut number = 1;
+    for loop_number in 1..DIVISOR as usize {
+        number = transform_once(number, subject_number);
+        if number == target_public_key {
+            return Some(loop_number);
+        }
+    }
+    None
+}
+
+/// The card's and the door's public keys.
+pub fn parse_input(input: &str) -> Result<(i64, i64)> {
+    let input = &parse::normalize(input);
+    let mut lines = input.lines();
+    let mut next_key = |name: &str| {
+        let l = lines
+            .next()
+            .ok_or_else(|| ParseError::at_end(input, format!("the {} public key", name)))?;
+        parse::number(input, l)
+    };
+    let card_pkey = next_key("card")?;
+    let door_pkey = next_key("door")?;
+    Ok((card_pkey, door_pkey))
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = (i64, i64);
+    type Params = Params;
+    type Part1 = i64;
+    type Part2 = &'static str;
+
+    fn parse(input: &str) -> Result<(i64, i64)> {
+        parse_input(input)
+    }
+
+    fn part1(public_keys: &(i64, i64)) -> Result<i64> {
+        Self::part1_with(public_keys, &Params::default())
+    }
+
+    // part 1 -- encryption key
+    fn part1_with(public_keys: &(i64, i64), params: &Params) -> Result<i64> {
+        let (card_pkey, door_pkey) = *public_keys;
+
+        let loop_size = |pkey| {
+            find_loop_size(params.subject, pkey).ok_or_else(|| {
+                format!(
+                    "no loop size makes public key {} from subject number {}",
+                    pkey, params.subject
+                )
+            })
+        };
+        let card_loop_size = loop_size(card_pkey)?;
+        let door_loop_size = loop_size(door_pkey)?;
+
+        log::info!("card loop size {}", card_loop_size);
+        log::info!("door loop size {}", door_loop_size);
+
+        let enc_key_1 = transform_number(card_pkey, door_loop_size);
+        let enc_key_2 = transform_number(door_pkey, card_loop_size);
+
+        if enc_key_1 != enc_key_2 {
+            return Err(format!("encryption keys differ: {} != {}", enc_key_1, enc_key_2).into());
+        }
+        Ok(enc_key_1)
+    }
+
+    // no part 2 on the last day
+    fn part2(_public_keys: &(i64, i64)) -> Result<&'static str> {
+        Ok("n/a")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    #[test]
+    fn example_correct() {
+        let initial_subject_number = 7;
+
+        let card_public_key = 5764801;
+        let card_loop_size = 8;
+        assert_eq!(
+            card_public_key,
+            transform_number(initial_subject_number, card_loop_size)
+        );
+
+        let door_public_key = 17807724;
+        let door_loop_size = 11;
+        assert_eq!(
+            door_public_key,
+            transform_number(initial_subject_number, door_loop_size)
+        );
+
+        let encryption_key_door = transform_number(door_public_key, card_loop_size);
+        let encryption_key_card = transform_number(card_public_key, door_loop_size);
+        assert_eq!(14897079, encryption_key_door);
+        assert_eq!(14897079, encryption_key_card);
+    }
+
+    #[test]
+    fn find_loop_size_correct() {
+        let initial_subject_number = 7;
+
+        let card_public_key = 5764801;
+        let card_loop_size = find_loop_size(initial_subject_number, card_public_key);
+        assert_eq!(card_loop_size, Some(8));
+
+        let door_public_key = 17807724;
+        let door_loop_size = find_loop_size(initial_subject_number, door_public_key);
+        assert_eq!(door_loop_size, Some(11));
+    }
+}
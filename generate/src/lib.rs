@@ -0,0 +1,139 @@
+//! Random puzzle inputs, reproducible from a seed, for stress tests and
+//! benchmarks at sizes the real inputs don't reach.
+
+use std::{fmt::Display, ops::RangeInclusive};
+
+use common::Solution;
+
+pub use fastrand::Rng;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Validity {
+    /// A puzzle with an answer, like the real inputs.
+    Valid,
+    /// A valid input with one line spoiled, so it must fail to parse.
+    Invalid,
+}
+
+/// A generated input, with the answers where they're known by construction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Generated {
+    pub fn new(input: String) -> Self {
+        Generated {
+            input,
+            part1: None,
+            part2: None,
+        }
+    }
+
+    pub fn with_part1(self, answer: impl Display) -> Self {
+        Generated {
+            part1: Some(answer.to_string()),
+            ..self
+        }
+    }
+
+    pub fn with_part2(self, answer: impl Display) -> Self {
+        Generated {
+            part2: Some(answer.to_string()),
+            ..self
+        }
+    }
+}
+
+/// A solution that can make up its own inputs.
+pub trait Generate: Solution {
+    /// What `size` counts, e.g. "passports".
+    const SIZE_OF: &'static str;
+    /// Roughly the size of a real input.
+    const DEFAULT_SIZE: usize;
+    /// The sizes `valid` can make.
+    const SIZES: RangeInclusive<usize> = 0..=usize::MAX;
+
+    /// A valid input of `size` things, for a `size` in `SIZES`.
+    fn valid(rng: &mut Rng, size: usize) -> Generated;
+
+    /// `line`, a non-blank line of a valid input, spoiled so that it no
+    /// longer parses.
+    fn corrupt(rng: &mut Rng, line: &str) -> String;
+}
+
+/// The input for `seed`; the same seed, size and validity always give the
+/// same input. A size the generator can't make is an error.
+pub fn generate<G: Generate>(
+    seed: u64,
+    size: usize,
+    validity: Validity,
+) -> common::Result<Generated> {
+    let (&min, &max) = (G::SIZES.start(), G::SIZES.end());
+    if size < min || size > max {
+        return Err(match max {
+            usize::MAX => format!("need at least {} {}, not {}", min, G::SIZE_OF, size),
+            _ => format!("can make {} to {} {}, not {}", min, max, G::SIZE_OF, size),
+        }
+        .into());
+    }
+    let mut rng = Rng::with_seed(seed);
+    let valid = G::valid(&mut rng, size);
+    Ok(match validity {
+        Validity::Valid => valid,
+        Validity::Invalid => Generated::new(corrupt_line(&mut rng, &valid.input, G::corrupt)),
+    })
+}
+
+// replaces one of the non-blank lines of `input`
+fn corrupt_line(
+    rng: &mut Rng,
+    input: &str,
+    corrupt: impl FnOnce(&mut Rng, &str) -> String,
+) -> String {
+    let lines: Vec<&str> = input.lines().collect();
+    let candidates: Vec<usize> = (0..lines.len())
+        .filter(|&i| !lines[i].trim().is_empty())
+        .collect();
+    let mut out: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    if let Some(&i) = candidates.get(rng.usize(..candidates.len().max(1))) {
+        out[i] = corrupt(rng, lines[i]);
+    }
+    out.join("\n") + "\n"
+}
+
+/// `line` with `insert` at a random character boundary.
+pub fn insert_at_random(rng: &mut Rng, line: &str, insert: &str) -> String {
+    let boundaries: Vec<usize> = line
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(line.len()))
+        .collect();
+    let at = boundaries[rng.usize(..boundaries.len())];
+    format!("{}{}{}", &line[..at], insert, &line[at..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_non_blank_lines_are_corrupted() {
+        for seed in 0..20 {
+            let mut rng = Rng::with_seed(seed);
+            let out = corrupt_line(&mut rng, "a\n\nb\n", |_, l| format!("<{}>", l));
+            assert!(out == "<a>\n\nb\n" || out == "a\n\n<b>\n", "{:?}", out);
+        }
+    }
+
+    #[test]
+    fn insertions_land_between_characters() {
+        let mut rng = Rng::with_seed(7);
+        for _ in 0..20 {
+            let out = insert_at_random(&mut rng, "é1", "x");
+            assert!(["xé1", "éx1", "é1x"].contains(&out.as_str()), "{}", out);
+        }
+    }
+}
@@ -0,0 +1,504 @@
+//! A birth/survival cellular automaton: each generation, a dead cell comes
+//! alive with one of the rule's birth counts of live neighbours, and a live
+//! cell survives with one of its survival counts.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    convert::TryFrom,
+    fmt,
+    hash::Hash,
+    ops::Add,
+    str::FromStr,
+};
+
+use common::{parse, ParseError};
+use serde::{Deserialize, Serialize};
+
+use crate::{Grid, Pos, Topology};
+
+/// The state of one cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum State {
+    /// Not part of the automaton, like the floor between seats: never
+    /// changes, never counts as a neighbour, and is seen through by line of
+    /// sight.
+    Void,
+    Dead,
+    Live,
+}
+
+/// The neighbour counts at which cells are born and survive, written in the
+/// usual notation, e.g. `B3/S23` for Conway's Life.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    birth: Vec<usize>,
+    survival: Vec<usize>,
+}
+
+impl Rule {
+    pub fn new(birth: &[usize], survival: &[usize]) -> Self {
+        Rule {
+            birth: birth.to_vec(),
+            survival: survival.to_vec(),
+        }
+    }
+
+    /// Whether dead cells with no live neighbours come alive (`B0`), so that
+    /// an unbounded world fills up everywhere at once.
+    pub fn births_from_nothing(&self) -> bool {
+        self.birth.contains(&0)
+    }
+
+    pub fn next(&self, state: State, live_neighbours: usize) -> State {
+        match state {
+            State::Void => State::Void,
+            State::Dead if self.birth.contains(&live_neighbours) => State::Live,
+            State::Live if !self.survival.contains(&live_neighbours) => State::Dead,
+            _ => state,
+        }
+    }
+}
+
+// one digit per count, as the notation has no separator
+fn counts(input: &str, digits: &str) -> Result<Vec<usize>, ParseError> {
+    digits
+        .char_indices()
+        .map(|(i, ch)| {
+            ch.to_digit(10)
+                .map(|d| d as usize)
+                .ok_or_else(|| ParseError::at(input, &digits[i..], "a neighbour count digit"))
+        })
+        .collect()
+}
+
+impl FromStr for Rule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let slash = s
+            .find('/')
+            .ok_or_else(|| ParseError::at(s, s, "a rule like \"B3/S23\""))?;
+        let birth = parse::tag(s, &s[..slash], "B")?;
+        let survival = parse::tag(s, &s[slash + 1..], "S")?;
+        Ok(Rule {
+            birth: counts(s, birth)?,
+            survival: counts(s, survival)?,
+        })
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits =
+            |counts: &[usize]| -> String { counts.iter().map(|c| c.to_string()).collect() };
+        write!(f, "B{}/S{}", digits(&self.birth), digits(&self.survival))
+    }
+}
+
+/// A position in N dimensions, for automata that aren't flat. Serialized
+/// as a list of its coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(into = "Vec<i64>", try_from = "Vec<i64>")]
+pub struct Point<const N: usize>(pub [i64; N]);
+
+impl<const N: usize> From<Point<N>> for Vec<i64> {
+    fn from(p: Point<N>) -> Self {
+        p.0.to_vec()
+    }
+}
+
+impl<const N: usize> TryFrom<Vec<i64>> for Point<N> {
+    type Error = String;
+
+    fn try_from(coords: Vec<i64>) -> Result<Self, Self::Error> {
+        let found = coords.len();
+        <[i64; N]>::try_from(coords)
+            .map(Point)
+            .map_err(|_| format!("expected {} coordinates, found {}", N, found))
+    }
+}
+
+impl<const N: usize> Default for Point<N> {
+    fn default() -> Self {
+        Point([0; N])
+    }
+}
+
+impl<const N: usize> Add for Point<N> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        for (a, b) in self.0.iter_mut().zip(rhs.0.iter()) {
+            *a += b;
+        }
+        self
+    }
+}
+
+/// Which cells count towards a cell's live neighbours: the first cell in
+/// the direction of each offset or, with line of sight, the first cell
+/// that isn't void.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Neighbourhood<P> {
+    offsets: Vec<P>,
+    line_of_sight: bool,
+}
+
+impl<P> Neighbourhood<P> {
+    pub fn offsets(&self) -> &[P] {
+        &self.offsets
+    }
+}
+
+impl Neighbourhood<Pos> {
+    pub fn adjacent(topology: Topology) -> Self {
+        Neighbourhood {
+            offsets: topology.offsets().to_vec(),
+            line_of_sight: false,
+        }
+    }
+
+    pub fn line_of_sight(topology: Topology) -> Self {
+        Neighbourhood {
+            offsets: topology.offsets().to_vec(),
+            line_of_sight: true,
+        }
+    }
+}
+
+impl<const N: usize> Neighbourhood<Point<N>> {
+    /// The 3^N - 1 cells within one step along every axis.
+    pub fn moore() -> Self {
+        let mut offsets = vec![Point::default()];
+        for axis in 0..N {
+            offsets = offsets
+                .into_iter()
+                .flat_map(|p: Point<N>| {
+                    (-1..=1).map(move |d| {
+                        let mut p = p;
+                        p.0[axis] = d;
+                        p
+                    })
+                })
+                .collect();
+        }
+        offsets.retain(|p| *p != Point::default());
+        Neighbourhood {
+            offsets,
+            line_of_sight: false,
+        }
+    }
+}
+
+impl<P: Copy + Add<Output = P>> Neighbourhood<P> {
+    pub fn count_live<S: Storage<Pos = P>>(&self, world: &S, pos: P) -> usize {
+        self.offsets
+            .iter()
+            .filter(|&&offset| {
+                let mut p = pos + offset;
+                loop {
+                    match world.get(p) {
+                        Some(State::Live) => return true,
+                        Some(State::Void) if self.line_of_sight => p = p + offset,
+                        _ => return false,
+                    }
+                }
+            })
+            .count()
+    }
+}
+
+/// How the cells of an automaton are held.
+pub trait Storage: Clone + Eq + Hash {
+    type Pos: Copy + Add<Output = Self::Pos>;
+
+    /// Whether the world goes on forever, so that a `B0` rule can't be run.
+    const UNBOUNDED: bool = false;
+
+    /// The state at `pos`, or `None` beyond the edge of a bounded world.
+    fn get(&self, pos: Self::Pos) -> Option<State>;
+
+    fn count_live(&self) -> usize;
+
+    /// The next generation, from each cell's next state; only cells within
+    /// `neighbourhood` of a live cell need be asked about.
+    fn next(
+        &self,
+        neighbourhood: &Neighbourhood<Self::Pos>,
+        state: impl Fn(Self::Pos) -> State,
+    ) -> Self;
+}
+
+/// Dense storage: a bounded grid of every cell, which may include void.
+impl Storage for Grid<State> {
+    type Pos = Pos;
+
+    fn get(&self, pos: Pos) -> Option<State> {
+        Grid::get(self, pos).copied()
+    }
+
+    fn count_live(&self) -> usize {
+        self.count(|&s| s == State::Live)
+    }
+
+    fn next(&self, _: &Neighbourhood<Pos>, state: impl Fn(Pos) -> State) -> Self {
+        Grid::from_fn(self.rows(), self.cols(), state)
+    }
+}
+
+/// Sparse storage: an unbounded world holding only its live cells. Only the
+/// cells near live ones are looked at, so it can't run a `B0` rule, which
+/// would bring every far-off dead cell to life.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct Sparse<P: Ord>(BTreeSet<P>);
+
+impl<P: Ord> Sparse<P> {
+    pub fn new(live: impl IntoIterator<Item = P>) -> Self {
+        Sparse(live.into_iter().collect())
+    }
+
+    pub fn live(&self) -> impl Iterator<Item = &P> {
+        self.0.iter()
+    }
+
+    pub fn is_live(&self, pos: &P) -> bool {
+        self.0.contains(pos)
+    }
+}
+
+impl<P: Copy + Ord + Hash + Add<Output = P>> Storage for Sparse<P> {
+    type Pos = P;
+
+    const UNBOUNDED: bool = true;
+
+    fn get(&self, pos: P) -> Option<State> {
+        match self.0.contains(&pos) {
+            true => Some(State::Live),
+            false => Some(State::Dead),
+        }
+    }
+
+    fn count_live(&self) -> usize {
+        self.0.len()
+    }
+
+    fn next(&self, neighbourhood: &Neighbourhood<P>, state: impl Fn(P) -> State) -> Self {
+        let candidates: BTreeSet<P> = self
+            .0
+            .iter()
+            .flat_map(|&p| neighbourhood.offsets().iter().map(move |&o| p + o))
+            .chain(self.0.iter().copied())
+            .collect();
+        Sparse(
+            candidates
+                .into_iter()
+                .filter(|&p| state(p) == State::Live)
+                .collect(),
+        )
+    }
+}
+
+/// Where a run of the automaton ended up repeating itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Settled {
+    /// Every generation from `generation` on is the same.
+    FixedPoint { generation: usize },
+    /// From `start` on, the generations repeat every `period`.
+    Cycle { start: usize, period: usize },
+}
+
+pub struct Automaton<S: Storage> {
+    world: S,
+    neighbourhood: Neighbourhood<S::Pos>,
+    rule: Rule,
+    generation: usize,
+}
+
+impl<S: Storage> Automaton<S> {
+    /// Panics on a `B0` rule with unbounded storage, which can't follow it.
+    pub fn new(world: S, neighbourhood: Neighbourhood<S::Pos>, rule: Rule) -> Self {
+        assert!(
+            !(S::UNBOUNDED && rule.births_from_nothing()),
+            "{} brings all of an unbounded world to life",
+            rule
+        );
+        Automaton {
+            world,
+            neighbourhood,
+            rule,
+            generation: 0,
+        }
+    }
+
+    pub fn world(&self) -> &S {
+        &self.world
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn count_live(&self) -> usize {
+        self.world.count_live()
+    }
+
+    pub fn step(&mut self) {
+        let (world, neighbourhood, rule) = (&self.world, &self.neighbourhood, &self.rule);
+        self.world = world.next(neighbourhood, |pos| {
+            let state = world.get(pos).unwrap_or(State::Void);
+            rule.next(state, neighbourhood.count_live(world, pos))
+        });
+        self.generation += 1;
+    }
+
+    pub fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+
+    /// Steps until a generation repeats an earlier one, giving up after
+    /// `limit` generations. Stops on the first repeat, so after a fixed
+    /// point the world is that fixed point.
+    pub fn settle(&mut self, limit: usize) -> Option<Settled> {
+        self.settle_observed(limit, |_| {})
+    }
+
+    /// As `settle`, showing `observe` each generation along the way,
+    /// starting with the current one.
+    pub fn settle_observed(
+        &mut self,
+        limit: usize,
+        mut observe: impl FnMut(&S),
+    ) -> Option<Settled> {
+        let mut seen = HashMap::new();
+        observe(&self.world);
+        seen.insert(self.world.clone(), self.generation);
+        for _ in 0..limit {
+            self.step();
+            observe(&self.world);
+            if let Some(&start) = seen.get(&self.world) {
+                let period = self.generation - start;
+                return Some(match period {
+                    1 => Settled::FixedPoint { generation: start },
+                    _ => Settled::Cycle { start, period },
+                });
+            }
+            seen.insert(self.world.clone(), self.generation);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life(rows: &str) -> Automaton<Sparse<Pos>> {
+        let grid: Grid<bool> = Grid::parse(rows).unwrap();
+        let live = grid.iter().filter(|(_, &v)| v).map(|(p, _)| p);
+        Automaton::new(
+            Sparse::new(live),
+            Neighbourhood::adjacent(Topology::Moore),
+            "B3/S23".parse().unwrap(),
+        )
+    }
+
+    #[test]
+    fn rule_notation() {
+        let rule: Rule = "B2/S12".parse().unwrap();
+        assert_eq!(Rule::new(&[2], &[1, 2]), rule);
+        assert_eq!("B2/S12", rule.to_string());
+        assert_eq!(State::Live, rule.next(State::Dead, 2));
+        assert_eq!(State::Dead, rule.next(State::Live, 0));
+        assert_eq!(State::Void, rule.next(State::Void, 2));
+        assert_eq!("B/S", Rule::new(&[], &[]).to_string());
+
+        let err = "B3/X23".parse::<Rule>().unwrap_err();
+        assert_eq!((1, 4), (err.line, err.column));
+        let err = "B3/S2x".parse::<Rule>().unwrap_err();
+        assert_eq!((1, 6), (err.line, err.column));
+    }
+
+    #[test]
+    fn block_is_a_fixed_point() {
+        let mut block = life("##\n##");
+        assert_eq!(
+            Some(Settled::FixedPoint { generation: 0 }),
+            block.settle(10)
+        );
+        assert_eq!(4, block.count_live());
+    }
+
+    #[test]
+    fn blinker_cycles() {
+        let mut blinker = life("...\n###\n...");
+        assert_eq!(
+            Some(Settled::Cycle {
+                start: 0,
+                period: 2
+            }),
+            blinker.settle(10)
+        );
+        assert_eq!(2, blinker.generation());
+    }
+
+    #[test]
+    fn glider_never_settles() {
+        let mut glider = life(".#.\n..#\n###");
+        assert_eq!(None, glider.settle(20));
+        assert_eq!(5, glider.count_live());
+    }
+
+    #[test]
+    #[should_panic(expected = "B03/S23 brings all of an unbounded world to life")]
+    fn sparse_refuses_births_from_nothing() {
+        Automaton::new(
+            Sparse::<Pos>::default(),
+            Neighbourhood::adjacent(Topology::Moore),
+            "B03/S23".parse().unwrap(),
+        );
+    }
+
+    #[test]
+    fn dense_runs_births_from_nothing() {
+        let mut automaton = Automaton::new(
+            Grid::new(2, 2, State::Dead),
+            Neighbourhood::adjacent(Topology::Moore),
+            "B0/S".parse().unwrap(),
+        );
+        automaton.step();
+        assert_eq!(4, automaton.count_live());
+    }
+
+    #[test]
+    fn line_of_sight_sees_past_void() {
+        let grid = Grid::from_fn(1, 4, |p| match p.col {
+            0 | 3 => State::Live,
+            _ => State::Void,
+        });
+        let origin = Pos::new(0, 0);
+        let adjacent = Neighbourhood::adjacent(Topology::Orthogonal);
+        let sight = Neighbourhood::line_of_sight(Topology::Orthogonal);
+        assert_eq!(0, adjacent.count_live(&grid, origin));
+        assert_eq!(1, sight.count_live(&grid, origin));
+    }
+
+    #[test]
+    fn moore_in_n_dimensions() {
+        assert_eq!(8, Neighbourhood::<Point<2>>::moore().offsets().len());
+        assert_eq!(26, Neighbourhood::<Point<3>>::moore().offsets().len());
+        assert_eq!(80, Neighbourhood::<Point<4>>::moore().offsets().len());
+    }
+
+    #[test]
+    fn points_serialize_as_coordinates() {
+        let world = Sparse::new(vec![Point([1, -2, 0]), Point([0, 0, 3])]);
+        let json = serde_json::to_string(&world).unwrap();
+        assert_eq!("[[0,0,3],[1,-2,0]]", json);
+        assert_eq!(world, serde_json::from_str(&json).unwrap());
+
+        let err = serde_json::from_str::<Point<3>>("[1,2]").unwrap_err();
+        assert!(err.to_string().contains("expected 3 coordinates, found 2"));
+    }
+}
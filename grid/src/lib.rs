@@ -0,0 +1,395 @@
+//! A rectangular grid of cells, as drawn in so many of the puzzle inputs.
+
+use std::{
+    convert::TryFrom,
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use common::ParseError;
+use serde::{Deserialize, Serialize};
+
+pub mod automaton;
+mod pos;
+mod topology;
+
+pub use pos::Pos;
+pub use topology::Topology;
+
+/// A cell that can be read from, and drawn as, a single character.
+pub trait Cell: Sized {
+    /// Describes the characters `from_char` accepts, for parse errors.
+    const EXPECTED: &'static str = "a grid cell";
+
+    fn from_char(c: char) -> Option<Self>;
+    fn to_char(&self) -> char;
+}
+
+impl Cell for char {
+    fn from_char(c: char) -> Option<Self> {
+        Some(c)
+    }
+
+    fn to_char(&self) -> char {
+        *self
+    }
+}
+
+// the usual convention: '#' is set, '.' is clear
+impl Cell for bool {
+    const EXPECTED: &'static str = "'.' or '#'";
+
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            true => '#',
+            false => '.',
+        }
+    }
+}
+
+/// Which edges of the grid wrap around to the opposite edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wrap {
+    Neither,
+    /// Columns wrap: the grid repeats to the left and right.
+    Cols,
+    /// Rows wrap: the grid repeats above and below.
+    Rows,
+    Both,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "Cells<T>")]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+// a grid as serialized, checked on the way in
+#[derive(Deserialize)]
+struct Cells<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> TryFrom<Cells<T>> for Grid<T> {
+    type Error = String;
+
+    fn try_from(c: Cells<T>) -> Result<Self, Self::Error> {
+        if c.cells.len() != c.rows * c.cols {
+            return Err(format!(
+                "{} cells don't fill {} rows of {}",
+                c.cells.len(),
+                c.rows,
+                c.cols
+            ));
+        }
+        Ok(Grid {
+            cells: c.cells,
+            rows: c.rows,
+            cols: c.cols,
+        })
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![fill; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..rows * cols)
+            .map(|i| f(Pos::new((i / cols) as i64, (i % cols) as i64)))
+            .collect();
+        Grid { cells, rows, cols }
+    }
+
+    /// Reads one row per line, mapping each character to a cell with `cell`;
+    /// `expected` describes the characters `cell` accepts.
+    pub fn parse_with(
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut lines = input.lines().peekable();
+        let cols = lines
+            .peek()
+            .ok_or_else(|| ParseError::at_end(input, "a row of the grid"))?
+            .chars()
+            .count();
+
+        let mut cells = Vec::new();
+        let mut rows = 0;
+        for line in lines {
+            if line.chars().count() != cols {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    format!("a row of {} cells", cols),
+                ));
+            }
+            for (i, c) in line.char_indices() {
+                let value = cell(c).ok_or_else(|| ParseError::at(input, &line[i..], expected))?;
+                cells.push(value);
+            }
+            rows += 1;
+        }
+        Ok(Grid { cells, rows, cols })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        (0..self.rows as i64).contains(&pos.row) && (0..self.cols as i64).contains(&pos.col)
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        if self.contains(pos) {
+            Some(pos.row as usize * self.cols + pos.col as usize)
+        } else {
+            None
+        }
+    }
+
+    /// The cell at `pos`, or `None` off the edge of the grid.
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(move |i| &mut self.cells[i])
+    }
+
+    /// The cell at `pos`, with the edges named by `wrap` joined to their
+    /// opposite edge; `None` off any edge that doesn't wrap.
+    pub fn get_wrapped(&self, pos: Pos, wrap: Wrap) -> Option<&T> {
+        if self.cells.is_empty() {
+            return None;
+        }
+        let (wrap_rows, wrap_cols) = match wrap {
+            Wrap::Neither => (false, false),
+            Wrap::Cols => (false, true),
+            Wrap::Rows => (true, false),
+            Wrap::Both => (true, true),
+        };
+        let row = match wrap_rows {
+            true => pos.row.rem_euclid(self.rows as i64),
+            false => pos.row,
+        };
+        let col = match wrap_cols {
+            true => pos.col.rem_euclid(self.cols as i64),
+            false => pos.col,
+        };
+        self.get(Pos::new(row, col))
+    }
+
+    /// Every position, a row at a time.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols;
+        (0..self.rows * cols).map(move |i| Pos::new((i / cols) as i64, (i % cols) as i64))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// The neighbours of `pos` that are on the grid.
+    pub fn neighbours(&self, pos: Pos, topology: Topology) -> impl Iterator<Item = (Pos, &T)> {
+        topology
+            .offsets()
+            .iter()
+            .filter_map(move |&offset| self.get(pos + offset).map(|v| (pos + offset, v)))
+    }
+
+    /// The first cell in each direction of `topology` that isn't skipped by
+    /// `transparent`, looking as far as the edge of the grid.
+    pub fn visible(
+        &self,
+        pos: Pos,
+        topology: Topology,
+        transparent: impl Fn(&T) -> bool,
+    ) -> impl Iterator<Item = (Pos, &T)> {
+        topology.offsets().iter().filter_map(move |&offset| {
+            let mut p = pos + offset;
+            while let Some(v) = self.get(p) {
+                if !transparent(v) {
+                    return Some((p, v));
+                }
+                p += offset;
+            }
+            None
+        })
+    }
+
+    pub fn count(&self, pred: impl Fn(&T) -> bool) -> usize {
+        self.cells.iter().filter(|&v| pred(v)).count()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    /// Copies `other` onto this grid with its top-left corner at `at`,
+    /// dropping any part that falls off the edge.
+    pub fn paste(&mut self, other: &Grid<T>, at: Pos)
+    where
+        T: Clone,
+    {
+        for (pos, v) in other.iter() {
+            if let Some(dst) = self.get_mut(at + pos) {
+                *dst = v.clone();
+            }
+        }
+    }
+}
+
+impl<T: Cell> Grid<T> {
+    /// Reads one row per line, using the cell type's character mapping.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::parse_with(input, T::EXPECTED, T::from_char)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.get(pos) {
+            Some(v) => v,
+            None => panic!("{:?} is outside a {}x{} grid", pos, self.rows, self.cols),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (rows, cols) = (self.rows, self.cols);
+        match self.get_mut(pos) {
+            Some(v) => v,
+            None => panic!("{:?} is outside a {}x{} grid", pos, rows, cols),
+        }
+    }
+}
+
+// renders back to the text it was parsed from, one line per row
+impl<T: Cell> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for r in 0..self.rows {
+            let line: String = self.row(r).iter().map(Cell::to_char).collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "#..\n.#.\n..#\n#.#\n";
+
+    #[test]
+    fn parse_and_render_round_trip() {
+        let grid: Grid<bool> = Grid::parse(EXAMPLE).unwrap();
+        assert_eq!((4, 3), (grid.rows(), grid.cols()));
+        assert_eq!(Some(&true), grid.get(Pos::new(1, 1)));
+        assert_eq!(Some(&false), grid.get(Pos::new(1, 2)));
+        assert_eq!(None, grid.get(Pos::new(4, 0)));
+        assert_eq!(EXAMPLE, grid.to_string());
+        assert_eq!(grid, Grid::parse(&grid.to_string()).unwrap());
+    }
+
+    #[test]
+    fn parse_errors_are_located() {
+        let err = Grid::<bool>::parse("#..\n.x.\n").unwrap_err();
+        assert_eq!((2, 2), (err.line, err.column));
+
+        let err = Grid::<bool>::parse("#..\n.#\n").unwrap_err();
+        assert_eq!((2, 1), (err.line, err.column));
+        assert_eq!("a row of 3 cells", err.expected);
+
+        assert!(Grid::<bool>::parse("").is_err());
+    }
+
+    #[test]
+    fn wrapping() {
+        let grid: Grid<char> = Grid::parse("abc\ndef\n").unwrap();
+        assert_eq!(Some(&'a'), grid.get_wrapped(Pos::new(0, 3), Wrap::Cols));
+        assert_eq!(Some(&'f'), grid.get_wrapped(Pos::new(1, -1), Wrap::Cols));
+        assert_eq!(None, grid.get_wrapped(Pos::new(2, 0), Wrap::Cols));
+        assert_eq!(Some(&'a'), grid.get_wrapped(Pos::new(2, 0), Wrap::Rows));
+        assert_eq!(Some(&'e'), grid.get_wrapped(Pos::new(-1, 4), Wrap::Both));
+        assert_eq!(None, grid.get_wrapped(Pos::new(0, 3), Wrap::Neither));
+    }
+
+    #[test]
+    fn neighbours_stay_on_the_grid() {
+        let grid: Grid<char> = Grid::parse("abc\ndef\nghi\n").unwrap();
+        let around =
+            |pos, topology| -> String { grid.neighbours(pos, topology).map(|(_, c)| *c).collect() };
+        assert_eq!("bdfh", around(Pos::new(1, 1), Topology::Orthogonal));
+        assert_eq!("abcdfghi", around(Pos::new(1, 1), Topology::Moore));
+        assert_eq!("abdfhi", around(Pos::new(1, 1), Topology::Hex));
+        assert_eq!("bde", around(Pos::new(0, 0), Topology::Moore));
+    }
+
+    #[test]
+    fn visible_looks_past_transparent_cells() {
+        let grid: Grid<char> = Grid::parse("a..b\n....\nc..d\n").unwrap();
+        let seen: String = grid
+            .visible(Pos::new(0, 0), Topology::Moore, |c| *c == '.')
+            .map(|(_, c)| *c)
+            .collect();
+        assert_eq!("bc", seen);
+    }
+
+    #[test]
+    fn paste_clips_to_the_grid() {
+        let mut grid = Grid::new(2, 3, '.');
+        grid.paste(&Grid::parse("ab\ncd\n").unwrap(), Pos::new(1, 2));
+        assert_eq!("...\n..a\n", grid.to_string());
+        assert_eq!('a', grid[Pos::new(1, 2)]);
+    }
+
+    #[test]
+    fn json_round_trip_is_checked() {
+        let grid: Grid<bool> = Grid::parse(EXAMPLE).unwrap();
+        let json = serde_json::to_string(&grid).unwrap();
+        assert_eq!(grid, serde_json::from_str(&json).unwrap());
+
+        let short = r#"{"cells":[true,false],"rows":2,"cols":2}"#;
+        let err = serde_json::from_str::<Grid<bool>>(short).unwrap_err();
+        assert!(err.to_string().contains("2 cells don't fill 2 rows of 2"));
+    }
+}
@@ -0,0 +1,56 @@
+use std::ops::{Add, AddAssign, Mul, Sub};
+
+use serde::{Deserialize, Serialize};
+
+/// A position on a grid. Signed, so that positions just off the edge (say,
+/// the neighbours of an edge cell) can be represented and rejected.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
+pub struct Pos {
+    pub row: i64,
+    pub col: i64,
+}
+
+impl Pos {
+    pub const fn new(row: i64, col: i64) -> Self {
+        Pos { row, col }
+    }
+}
+
+impl Add for Pos {
+    type Output = Pos;
+
+    fn add(self, rhs: Pos) -> Pos {
+        Pos::new(self.row + rhs.row, self.col + rhs.col)
+    }
+}
+
+impl AddAssign for Pos {
+    fn add_assign(&mut self, rhs: Pos) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Pos {
+    type Output = Pos;
+
+    fn sub(self, rhs: Pos) -> Pos {
+        Pos::new(self.row - rhs.row, self.col - rhs.col)
+    }
+}
+
+// scaling an offset, e.g. to step several times in one direction
+impl Mul<i64> for Pos {
+    type Output = Pos;
+
+    fn mul(self, rhs: i64) -> Pos {
+        Pos::new(self.row * rhs, self.col * rhs)
+    }
+}
+
+impl From<(i64, i64)> for Pos {
+    fn from((row, col): (i64, i64)) -> Self {
+        Pos::new(row, col)
+    }
+}
@@ -0,0 +1,53 @@
+use crate::Pos;
+
+/// Which cells count as a cell's neighbours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Topology {
+    /// The four cells sharing an edge.
+    Orthogonal,
+    /// The eight cells sharing an edge or a corner.
+    Moore,
+    /// The six cells of a hexagonal grid in axial coordinates, where each
+    /// row is shifted half a cell from the one below: the neighbours are
+    /// those sharing an edge, plus the two along the `(1, 1)` diagonal.
+    Hex,
+}
+
+const ORTHOGONAL: [Pos; 4] = [
+    Pos::new(-1, 0),
+    Pos::new(0, -1),
+    Pos::new(0, 1),
+    Pos::new(1, 0),
+];
+
+const MOORE: [Pos; 8] = [
+    Pos::new(-1, -1),
+    Pos::new(-1, 0),
+    Pos::new(-1, 1),
+    Pos::new(0, -1),
+    Pos::new(0, 1),
+    Pos::new(1, -1),
+    Pos::new(1, 0),
+    Pos::new(1, 1),
+];
+
+const HEX: [Pos; 6] = [
+    Pos::new(-1, -1),
+    Pos::new(-1, 0),
+    Pos::new(0, -1),
+    Pos::new(0, 1),
+    Pos::new(1, 0),
+    Pos::new(1, 1),
+];
+
+impl Topology {
+    /// The offset from a cell to each of its neighbours; also the
+    /// directions to look along for line of sight.
+    pub fn offsets(&self) -> &'static [Pos] {
+        match self {
+            Topology::Orthogonal => &ORTHOGONAL,
+            Topology::Moore => &MOORE,
+            Topology::Hex => &HEX,
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! Pictures of the simulations: frames of coloured cells, written out as
+//! PNG or SVG sequences, or as an animated GIF.
+
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use common::Solution;
+use grid::{Grid, Pos};
+
+mod raster;
+mod svg;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const WHITE: Rgb = Rgb(0xff, 0xff, 0xff);
+    pub const BLACK: Rgb = Rgb(0x00, 0x00, 0x00);
+}
+
+/// How cells are arranged on the page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    Square,
+    /// Hexagons in axial coordinates, as in `grid::Topology::Hex`: each row
+    /// sits half a cell to the right of the one below it.
+    Hex,
+}
+
+/// One picture: a colour for each cell of a grid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    cells: Grid<Rgb>,
+    layout: Layout,
+    background: Rgb,
+}
+
+impl Frame {
+    pub fn new<T>(grid: &Grid<T>, layout: Layout, colour: impl Fn(&T) -> Rgb) -> Self {
+        Frame {
+            cells: grid.map(colour),
+            layout,
+            background: Rgb::WHITE,
+        }
+    }
+
+    /// The colour around and between the cells.
+    pub fn with_background(self, background: Rgb) -> Self {
+        Frame { background, ..self }
+    }
+
+    /// Lays square frames out in rows, `gap` cells apart, e.g. to show
+    /// every slice of a 3D or 4D grid at once. Each frame takes the space of
+    /// the largest.
+    pub fn tiled(rows: &[Vec<Frame>], gap: usize, background: Rgb) -> Self {
+        let all = || rows.iter().flatten();
+        let height = all().map(|f| f.cells.rows()).max().unwrap_or(0) + gap;
+        let width = all().map(|f| f.cells.cols()).max().unwrap_or(0) + gap;
+        let across = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        let mut cells = Grid::new(
+            (rows.len() * height).saturating_sub(gap),
+            (across * width).saturating_sub(gap),
+            background,
+        );
+        for (r, row) in rows.iter().enumerate() {
+            for (c, f) in row.iter().enumerate() {
+                let at = Pos::new((r * height) as i64, (c * width) as i64);
+                cells.paste(&f.cells, at);
+            }
+        }
+        Frame {
+            cells,
+            layout: Layout::Square,
+            background,
+        }
+    }
+
+    pub fn cells(&self) -> &Grid<Rgb> {
+        &self.cells
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    pub fn background(&self) -> Rgb {
+        self.background
+    }
+}
+
+/// A solution whose working can be watched, as the frames of each part.
+pub trait Animate: Solution {
+    fn frames(input: &Self::Input, params: &Self::Params, part: u8) -> common::Result<Vec<Frame>>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// One PNG file per frame
+    Png,
+    /// One animated GIF
+    Gif,
+    /// One SVG file per frame
+    Svg,
+}
+
+/// Options for drawing frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    /// Size of a cell, in pixels.
+    pub scale: u32,
+    /// Time each frame is shown in an animation, in hundredths of a second.
+    pub delay: u16,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "png" => Ok(Format::Png),
+            "gif" => Ok(Format::Gif),
+            "svg" => Ok(Format::Svg),
+            _ => Err(format!(
+                "unknown image format {:?}; expected png, gif or svg",
+                s
+            )),
+        }
+    }
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Style {
+            scale: 4,
+            delay: 10,
+        }
+    }
+}
+
+/// Writes `frames` to `output`: a GIF file, or a directory to fill with
+/// numbered PNG or SVG files. Returns the files written.
+pub fn export(
+    frames: &[Frame],
+    format: Format,
+    output: &Path,
+    style: Style,
+) -> common::Result<Vec<PathBuf>> {
+    if frames.is_empty() {
+        return Err("no frames to export".into());
+    }
+    let create = |path: &Path| {
+        File::create(path)
+            .map(BufWriter::new)
+            .map_err(|e| format!("failed to create {}: {}", path.display(), e))
+    };
+
+    match format {
+        Format::Gif => {
+            raster::write_gif(create(output)?, frames, style)?;
+            Ok(vec![output.to_path_buf()])
+        }
+        Format::Png | Format::Svg => {
+            fs::create_dir_all(output)
+                .map_err(|e| format!("failed to create {}: {}", output.display(), e))?;
+            let extension = match format {
+                Format::Png => "png",
+                _ => "svg",
+            };
+            let mut written = Vec::new();
+            for (i, frame) in frames.iter().enumerate() {
+                let path = output.join(format!("frame-{:04}.{}", i, extension));
+                match format {
+                    Format::Png => raster::write_png(create(&path)?, frame, style)?,
+                    _ => svg::write(create(&path)?, frame, style)?,
+                }
+                written.push(path);
+            }
+            Ok(written)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tiles_are_spaced_by_the_largest() {
+        let frame = |rows: &str| {
+            let grid: Grid<bool> = Grid::parse(rows).unwrap();
+            Frame::new(&grid, Layout::Square, |&v| match v {
+                true => Rgb::BLACK,
+                false => Rgb::WHITE,
+            })
+        };
+        let grey = Rgb(0x80, 0x80, 0x80);
+        let tiled = Frame::tiled(
+            &[vec![frame("##\n##"), frame("#")], vec![frame(".")]],
+            1,
+            grey,
+        );
+        let cells = tiled.cells();
+        assert_eq!((5, 5), (cells.rows(), cells.cols()));
+        assert_eq!(Rgb::BLACK, cells[Pos::new(1, 1)]);
+        assert_eq!(grey, cells[Pos::new(1, 2)]);
+        assert_eq!(Rgb::BLACK, cells[Pos::new(0, 3)]);
+        assert_eq!(grey, cells[Pos::new(1, 3)]);
+        assert_eq!(Rgb::WHITE, cells[Pos::new(3, 0)]);
+    }
+}
@@ -0,0 +1,247 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    io::Write,
+    iter,
+};
+
+use crate::{Frame, Layout, Rgb, Style};
+
+// the most pixels held in memory for one image
+const MAX_PIXELS: usize = 1 << 28;
+
+// the widest and tallest image an encoder takes
+struct Limit {
+    encoder: &'static str,
+    max_side: u32,
+}
+
+const PNG: Limit = Limit {
+    encoder: "PNG",
+    max_side: i32::MAX as u32,
+};
+
+const GIF: Limit = Limit {
+    encoder: "GIF",
+    max_side: u16::MAX as u32,
+};
+
+impl Limit {
+    // the size as u32s, if the encoder takes it and it fits in memory;
+    // `None` for a side that overflowed working it out
+    fn check(&self, width: Option<usize>, height: Option<usize>) -> common::Result<(u32, u32)> {
+        let fits = |side: usize| side <= self.max_side as usize;
+        match (width, height) {
+            (Some(w), Some(h)) if fits(w) && fits(h) => match w.checked_mul(h) {
+                Some(pixels) if pixels <= MAX_PIXELS => Ok((w as u32, h as u32)),
+                _ => Err(format!("{}x{} is more than {} pixels", w, h, MAX_PIXELS).into()),
+            },
+            (Some(w), Some(h)) => {
+                Err(format!("{}x{} is too large for a {}", w, h, self.encoder).into())
+            }
+            _ => Err(format!("the image is too large for a {}", self.encoder).into()),
+        }
+    }
+}
+
+/// A frame drawn as pixels, a row at a time.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<Rgb>,
+}
+
+impl Image {
+    fn blank(width: u32, height: u32, background: Rgb) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![background; (width * height) as usize],
+        }
+    }
+
+    // hexagons are drawn as squares, with the half-cell shift between rows;
+    // a frame without cells is an image without pixels, and one too large
+    // for the encoder is an error before anything is drawn
+    fn draw(frame: &Frame, scale: u32, limit: &Limit) -> common::Result<Self> {
+        let cells = frame.cells();
+        if cells.rows() == 0 || cells.cols() == 0 {
+            return Ok(Image::blank(0, 0, frame.background()));
+        }
+        let hex_shift = match frame.layout() {
+            Layout::Square => Some(0),
+            Layout::Hex => (cells.rows() - 1)
+                .checked_mul(scale as usize)
+                .map(|s| s / 2),
+        };
+        let width = cells.cols().checked_mul(scale as usize);
+        let width = width.zip(hex_shift).and_then(|(w, s)| w.checked_add(s));
+        let height = cells.rows().checked_mul(scale as usize);
+        let (width, height) = limit.check(width, height)?;
+
+        // within the limits, none of this overflows
+        let rows = cells.rows() as u32;
+        let shift = |row: u32| match frame.layout() {
+            Layout::Square => 0,
+            Layout::Hex => (rows - 1 - row) * scale / 2,
+        };
+        let mut image = Image::blank(width, height, frame.background());
+        for (pos, &colour) in cells.iter() {
+            let (row, col) = (pos.row as u32, pos.col as u32);
+            let x0 = col * scale + shift(row);
+            for y in row * scale..(row + 1) * scale {
+                let start = (y * width + x0) as usize;
+                image.pixels[start..start + scale as usize].fill(colour);
+            }
+        }
+        Ok(image)
+    }
+
+    // copied to the top left of a larger image, so frames that grow can
+    // share one size
+    fn padded(&self, width: u32, height: u32, background: Rgb) -> Self {
+        let mut image = Image::blank(width, height, background);
+        for y in 0..self.height {
+            let src = (y * self.width) as usize;
+            let dst = (y * width) as usize;
+            image.pixels[dst..dst + self.width as usize]
+                .copy_from_slice(&self.pixels[src..src + self.width as usize]);
+        }
+        image
+    }
+}
+
+pub(crate) fn write_png(out: impl Write, frame: &Frame, style: Style) -> common::Result<()> {
+    let image = Image::draw(frame, style.scale, &PNG)?;
+    let mut encoder = png::Encoder::new(out, image.width, image.height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let data: Vec<u8> = image
+        .pixels
+        .iter()
+        .flat_map(|&Rgb(r, g, b)| vec![r, g, b])
+        .collect();
+    encoder.write_header()?.write_image_data(&data)?;
+    Ok(())
+}
+
+// GIF palette limit
+const MAX_COLOURS: usize = 256;
+
+pub(crate) fn write_gif(out: impl Write, frames: &[Frame], style: Style) -> common::Result<()> {
+    let images = frames.iter().map(|f| Image::draw(f, style.scale, &GIF));
+    let images: Vec<Image> = images.collect::<common::Result<_>>()?;
+    // every frame is padded to the largest width and height, which may be
+    // from different frames
+    let width = images.iter().map(|i| i.width as usize).max().unwrap_or(0);
+    let height = images.iter().map(|i| i.height as usize).max().unwrap_or(0);
+    let (width, height) = GIF.check(Some(width), Some(height))?;
+
+    // one palette shared by every frame, in order of first use; frames are
+    // padded with the first frame's background
+    let background = frames[0].background();
+    let mut palette: HashMap<Rgb, u8> = HashMap::new();
+    let mut colours = Vec::new();
+    let used = images.iter().flat_map(|i| i.pixels.iter());
+    for &colour in used.chain(iter::once(&background)) {
+        if let Entry::Vacant(entry) = palette.entry(colour) {
+            if colours.len() == MAX_COLOURS {
+                return Err(format!("more than {} colours for a GIF", MAX_COLOURS).into());
+            }
+            entry.insert(colours.len() as u8);
+            colours.push(colour);
+        }
+    }
+    let palette_bytes: Vec<u8> = colours
+        .iter()
+        .flat_map(|&Rgb(r, g, b)| vec![r, g, b])
+        .collect();
+
+    let mut encoder = gif::Encoder::new(out, width as u16, height as u16, &palette_bytes)?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+    for image in images {
+        let image = image.padded(width, height, background);
+        let frame = gif::Frame {
+            width: width as u16,
+            height: height as u16,
+            delay: style.delay,
+            buffer: image.pixels.iter().map(|c| palette[c]).collect(),
+            ..gif::Frame::default()
+        };
+        encoder.write_frame(&frame)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grid::Grid;
+
+    fn frame(layout: Layout) -> Frame {
+        let grid: Grid<bool> = Grid::parse("#.\n.#").unwrap();
+        Frame::new(&grid, layout, |&v| match v {
+            true => Rgb::BLACK,
+            false => Rgb::WHITE,
+        })
+    }
+
+    #[test]
+    fn cells_are_scaled() {
+        let image = Image::draw(&frame(Layout::Square), 2, &PNG).unwrap();
+        assert_eq!((4, 4), (image.width, image.height));
+        assert_eq!(Rgb::BLACK, image.pixels[0]);
+        assert_eq!(Rgb::BLACK, image.pixels[5]);
+        assert_eq!(Rgb::WHITE, image.pixels[2]);
+        assert_eq!(Rgb::BLACK, image.pixels[15]);
+    }
+
+    #[test]
+    fn hex_rows_are_shifted() {
+        let image = Image::draw(&frame(Layout::Hex), 2, &PNG).unwrap();
+        // the top row sits half a cell right of the bottom row
+        assert_eq!((5, 4), (image.width, image.height));
+        assert_eq!(Rgb::WHITE, image.pixels[0]);
+        assert_eq!(Rgb::BLACK, image.pixels[1]);
+        assert_eq!(Rgb::BLACK, image.pixels[3 * 5 + 3]);
+    }
+
+    #[test]
+    fn empty_frames_are_empty_images() {
+        let grid: Grid<bool> = Grid::from_fn(0, 0, |_| true);
+        for layout in [Layout::Square, Layout::Hex] {
+            let frame = Frame::new(&grid, layout, |_| Rgb::BLACK);
+            let image = Image::draw(&frame, 2, &GIF).unwrap();
+            assert_eq!((0, 0), (image.width, image.height));
+            assert!(image.pixels.is_empty());
+        }
+    }
+
+    #[test]
+    fn oversized_images_are_refused_before_drawing() {
+        for layout in [Layout::Square, Layout::Hex] {
+            // overflowing, too wide for a GIF, and too many pixels
+            assert!(Image::draw(&frame(layout), u32::MAX, &PNG).is_err());
+            assert!(Image::draw(&frame(layout), 40_000, &GIF).is_err());
+            assert!(Image::draw(&frame(layout), 20_000, &PNG).is_err());
+        }
+        let style = Style {
+            scale: u32::MAX,
+            ..Style::default()
+        };
+        assert!(write_png(Vec::new(), &frame(Layout::Hex), style).is_err());
+        assert!(write_gif(Vec::new(), &[frame(Layout::Hex)], style).is_err());
+    }
+
+    #[test]
+    fn encodes_png_and_gif() {
+        let mut png = Vec::new();
+        write_png(&mut png, &frame(Layout::Square), Style::default()).unwrap();
+        assert_eq!(b"\x89PNG", &png[..4]);
+
+        let mut gif = Vec::new();
+        let frames = [frame(Layout::Square), frame(Layout::Hex)];
+        write_gif(&mut gif, &frames, Style::default()).unwrap();
+        assert_eq!(b"GIF89a", &gif[..6]);
+    }
+}
@@ -0,0 +1,106 @@
+use std::io::Write;
+
+use crate::{Frame, Layout, Rgb, Style};
+
+// height of a row of hexagons, relative to their width
+const HEX_ROW_HEIGHT: f64 = 0.866_025_403_784_438_6; // sqrt(3) / 2
+
+fn colour(Rgb(r, g, b): Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+// pointy-topped hexagons, `size` across the flats, touching their
+// neighbours; only cells that differ from the background are drawn
+pub(crate) fn write(mut out: impl Write, frame: &Frame, style: Style) -> common::Result<()> {
+    let cells = frame.cells();
+    let size = style.scale as f64;
+    let (rows, cols) = (cells.rows() as f64, cells.cols() as f64);
+    let (width, height) = match frame.layout() {
+        Layout::Square => (cols * size, rows * size),
+        Layout::Hex => (
+            (cols + (rows - 1.0) / 2.0) * size,
+            ((rows - 1.0) * HEX_ROW_HEIGHT + 2.0 / 3f64.sqrt()) * size,
+        ),
+    };
+
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = width,
+        h = height
+    )?;
+    writeln!(
+        out,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        colour(frame.background())
+    )?;
+    for (pos, &fill) in cells.iter().filter(|(_, &c)| c != frame.background()) {
+        let (row, col) = (pos.row as f64, pos.col as f64);
+        match frame.layout() {
+            Layout::Square => writeln!(
+                out,
+                r#"<rect x="{}" y="{}" width="{s}" height="{s}" fill="{}"/>"#,
+                col * size,
+                row * size,
+                colour(fill),
+                s = size
+            )?,
+            Layout::Hex => {
+                let cx = (col + (rows - 1.0 - row) / 2.0 + 0.5) * size;
+                let cy = (row * HEX_ROW_HEIGHT + 1.0 / 3f64.sqrt()) * size;
+                let radius = size / 3f64.sqrt();
+                let points: Vec<String> = (0..6)
+                    .map(|i| {
+                        let angle = (60.0 * i as f64 + 30.0).to_radians();
+                        format!(
+                            "{:.2},{:.2}",
+                            cx + radius * angle.cos(),
+                            cy + radius * angle.sin()
+                        )
+                    })
+                    .collect();
+                writeln!(
+                    out,
+                    r#"<polygon points="{}" fill="{}"/>"#,
+                    points.join(" "),
+                    colour(fill)
+                )?
+            }
+        }
+    }
+    writeln!(out, "</svg>")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grid::Grid;
+
+    #[test]
+    fn draws_cells_that_differ_from_the_background() {
+        let grid: Grid<bool> = Grid::parse("#.\n.#").unwrap();
+        let frame = Frame::new(&grid, Layout::Square, |&v| match v {
+            true => Rgb(0x12, 0x34, 0x56),
+            false => Rgb::WHITE,
+        });
+        let mut out = Vec::new();
+        write(&mut out, &frame, Style::default()).unwrap();
+        let svg = String::from_utf8(out).unwrap();
+
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(2, svg.matches(r##"fill="#123456""##).count());
+        assert!(svg.contains(r#"<rect x="4" y="4" width="4" height="4""#));
+
+        // every cell differs from a black background, and hexagons are polygons
+        let hex = Frame {
+            layout: Layout::Hex,
+            ..frame.with_background(Rgb::BLACK)
+        };
+        let mut out = Vec::new();
+        write(&mut out, &hex, Style::default()).unwrap();
+        let svg = String::from_utf8(out).unwrap();
+        assert_eq!(4, svg.matches("<polygon").count());
+        assert_eq!(0, svg.matches("<rect x").count());
+    }
+}
@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+// speed limits, so holding a key down can't stall or flood the terminal
+const MIN_DELAY: Duration = Duration::from_millis(5);
+const MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// What the viewer asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    PlayPause,
+    Step,
+    Faster,
+    Slower,
+    Quit,
+}
+
+/// Whether the simulation is playing, and how fast; apart from the terminal
+/// so it can be tested.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Controls {
+    pub playing: bool,
+    pub delay: Duration,
+    pub finished: bool,
+    pub quit: bool,
+    /// Steps taken so far.
+    pub steps: usize,
+}
+
+impl Controls {
+    /// Paused at the start, playing a step every `delay` once started.
+    pub fn new(delay: Duration) -> Self {
+        Controls {
+            playing: false,
+            delay: delay.clamp(MIN_DELAY, MAX_DELAY),
+            finished: false,
+            quit: false,
+            steps: 0,
+        }
+    }
+
+    /// Applies `command`; true if the simulation should take a step now.
+    pub fn apply(&mut self, command: Command) -> bool {
+        match command {
+            Command::PlayPause => self.playing = !self.playing && !self.finished,
+            Command::Step => {
+                self.playing = false;
+                return !self.finished;
+            }
+            Command::Faster => self.delay = (self.delay / 2).max(MIN_DELAY),
+            Command::Slower => self.delay = (self.delay * 2).min(MAX_DELAY),
+            Command::Quit => self.quit = true,
+        }
+        false
+    }
+
+    /// Records a step, and whether the simulation had anything left to do.
+    pub fn stepped(&mut self, more: bool) {
+        if more {
+            self.steps += 1;
+        } else {
+            self.finished = true;
+            self.playing = false;
+        }
+    }
+
+    pub fn status(&self) -> String {
+        let state = match (self.finished, self.playing) {
+            (true, _) => "finished",
+            (false, true) => "playing",
+            (false, false) => "paused",
+        };
+        format!(
+            "step {} | {} | {} ms/step | space: play/pause  \u{2192}/n: step  +/-: speed  q: quit",
+            self.steps,
+            state,
+            self.delay.as_millis()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn play_pause_and_step() {
+        let mut c = Controls::new(Duration::from_millis(100));
+        assert!(!c.playing);
+        assert!(!c.apply(Command::PlayPause));
+        assert!(c.playing);
+
+        // stepping pauses
+        assert!(c.apply(Command::Step));
+        assert!(!c.playing);
+        c.stepped(true);
+        assert_eq!(1, c.steps);
+
+        // nothing more to do
+        c.apply(Command::PlayPause);
+        c.stepped(false);
+        assert!(c.finished && !c.playing);
+        assert!(!c.apply(Command::Step));
+        c.apply(Command::PlayPause);
+        assert!(!c.playing);
+        assert_eq!(1, c.steps);
+    }
+
+    #[test]
+    fn speed_is_limited() {
+        let mut c = Controls::new(Duration::from_millis(100));
+        c.apply(Command::Faster);
+        assert_eq!(Duration::from_millis(50), c.delay);
+        for _ in 0..20 {
+            c.apply(Command::Faster);
+        }
+        assert_eq!(MIN_DELAY, c.delay);
+        for _ in 0..20 {
+            c.apply(Command::Slower);
+        }
+        assert_eq!(MAX_DELAY, c.delay);
+
+        c.apply(Command::Quit);
+        assert!(c.quit);
+    }
+}
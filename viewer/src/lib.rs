@@ -0,0 +1,35 @@
+//! Watching a puzzle play out in the terminal, a step at a time.
+
+use std::time::Duration;
+
+use common::Solution;
+
+mod controls;
+mod terminal;
+
+pub use controls::{Command, Controls};
+
+/// A puzzle's working, as a sequence of text screens.
+pub trait Simulation {
+    /// The current state, as text.
+    fn screen(&self) -> String;
+
+    /// Advances one step; false once there is nothing more to do.
+    fn step(&mut self) -> bool;
+}
+
+/// A solution whose working can be watched, one simulation per part.
+pub trait Watch: Solution {
+    fn simulation(
+        input: &Self::Input,
+        params: &Self::Params,
+        part: u8,
+    ) -> common::Result<Box<dyn Simulation>>;
+}
+
+/// Shows `simulation` full screen until the viewer quits: space plays and
+/// pauses, the right arrow (or `n`) steps, `+` and `-` change the speed and
+/// `q` quits.
+pub fn watch(simulation: &mut dyn Simulation, title: &str, delay: Duration) -> common::Result<()> {
+    terminal::run(simulation, title, Controls::new(delay))
+}
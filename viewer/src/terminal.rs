@@ -0,0 +1,109 @@
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+
+use crate::{Command, Controls, Simulation};
+
+fn command(key: KeyEvent) -> Option<Command> {
+    if key.kind == KeyEventKind::Release {
+        return None;
+    }
+    match key.code {
+        KeyCode::Char(' ') | KeyCode::Char('p') => Some(Command::PlayPause),
+        KeyCode::Right | KeyCode::Char('n') | KeyCode::Char('.') => Some(Command::Step),
+        KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Up => Some(Command::Faster),
+        KeyCode::Char('-') | KeyCode::Down => Some(Command::Slower),
+        KeyCode::Char('q') | KeyCode::Esc => Some(Command::Quit),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Command::Quit),
+        _ => None,
+    }
+}
+
+// the whole screen, top left first, cut to fit the terminal rather than
+// scrolling it
+fn draw(out: &mut impl Write, title: &str, screen: &str, controls: &Controls) -> io::Result<()> {
+    let (cols, rows) = terminal::size()?;
+    let fit = |line: &str| -> String { line.chars().take(cols as usize).collect() };
+    let body = (rows as usize).saturating_sub(3);
+
+    queue!(out, cursor::MoveTo(0, 0), terminal::Clear(ClearType::All))?;
+    queue!(out, Print(fit(title)), Print("\r\n\r\n"))?;
+    for line in screen.lines().take(body) {
+        queue!(out, Print(fit(line)), Print("\r\n"))?;
+    }
+    queue!(
+        out,
+        cursor::MoveTo(0, rows.saturating_sub(1)),
+        Print(fit(&controls.status()))
+    )?;
+    out.flush()
+}
+
+fn play(
+    out: &mut impl Write,
+    simulation: &mut dyn Simulation,
+    title: &str,
+    mut controls: Controls,
+) -> common::Result<()> {
+    let mut next_step = Instant::now();
+    draw(out, title, &simulation.screen(), &controls)?;
+    while !controls.quit {
+        let wait = match controls.playing {
+            true => next_step.saturating_duration_since(Instant::now()),
+            false => Duration::from_secs(60),
+        };
+        let mut step = false;
+        if event::poll(wait)? {
+            match event::read()? {
+                Event::Key(key) => match command(key) {
+                    Some(c) => step = controls.apply(c),
+                    None => continue,
+                },
+                Event::Resize(_, _) => {}
+                _ => continue,
+            }
+        } else if controls.playing {
+            step = true;
+            next_step = Instant::now() + controls.delay;
+        }
+        if step {
+            controls.stepped(simulation.step());
+        }
+        draw(out, title, &simulation.screen(), &controls)?;
+    }
+    Ok(())
+}
+
+// puts the terminal back when dropped, so it's restored however the viewer
+// stops, a panic included; there's nothing to be done if that fails
+struct Restore;
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let mut out = io::stdout();
+        let _ = queue!(out, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = out.flush();
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+pub(crate) fn run(
+    simulation: &mut dyn Simulation,
+    title: &str,
+    controls: Controls,
+) -> common::Result<()> {
+    let mut out = io::stdout();
+    terminal::enable_raw_mode()?;
+    let _restore = Restore;
+    queue!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+    play(&mut out, simulation, title, controls)
+}